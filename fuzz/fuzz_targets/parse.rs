@@ -0,0 +1,11 @@
+#![no_main]
+
+use duration_flex::DurationFlex;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+	// Parsing must never panic, and whatever is accepted must survive a round-trip through `Display`.
+	if let Ok(value) = DurationFlex::try_from(input) {
+		assert_eq!(DurationFlex::try_from(value.to_string().as_str()).unwrap(), value);
+	}
+});
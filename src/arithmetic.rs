@@ -0,0 +1,486 @@
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use chrono::Duration;
+
+use crate::{DurationFlex, DurationFlexError, NANOS_PER_SEC};
+
+/// Smallest and largest durations, in nano-seconds.
+const MIN_NANOS: i128 = DurationFlex::MIN.total_nanos();
+const MAX_NANOS: i128 = DurationFlex::MAX.total_nanos();
+
+/// Amount of distinct durations, which is the modulus of the overflowing operations.
+const RANGE: u128 = (MAX_NANOS - MIN_NANOS) as u128 + 1;
+
+impl DurationFlex {
+	/// `self + rhs`, or `None` on overflow.
+	pub fn checked_add(self, rhs: DurationFlex) -> Option<DurationFlex> {
+		DurationFlex::from_total_nanos(self.total_nanos() + rhs.total_nanos())
+	}
+
+	/// `self - rhs`, or `None` on overflow.
+	pub fn checked_sub(self, rhs: DurationFlex) -> Option<DurationFlex> {
+		DurationFlex::from_total_nanos(self.total_nanos() - rhs.total_nanos())
+	}
+
+	/// `self * rhs`, or `None` on overflow.
+	pub fn checked_mul(self, rhs: i64) -> Option<DurationFlex> {
+		self.total_nanos().checked_mul(rhs as i128).and_then(DurationFlex::from_total_nanos)
+	}
+
+	/// `self / rhs`, truncated towards zero, or `None` if `rhs` is zero or on overflow.
+	pub fn checked_div(self, rhs: i64) -> Option<DurationFlex> {
+		self.total_nanos().checked_div(rhs as i128).and_then(DurationFlex::from_total_nanos)
+	}
+
+	/// Remainder of `self / rhs`, with the sign of `self`, or `None` if `rhs` is zero.
+	pub fn checked_rem(self, rhs: DurationFlex) -> Option<DurationFlex> {
+		self.total_nanos().checked_rem(rhs.total_nanos()).and_then(DurationFlex::from_total_nanos)
+	}
+
+	/// `-self`, or `None` on overflow, which only happens for the smallest duration.
+	pub fn checked_neg(self) -> Option<DurationFlex> {
+		DurationFlex::from_total_nanos(-self.total_nanos())
+	}
+
+	/// `self + rhs`, limited to the smallest or largest duration on overflow.
+	pub fn saturating_add(self, rhs: DurationFlex) -> DurationFlex {
+		saturate(self.total_nanos() + rhs.total_nanos())
+	}
+
+	/// `self - rhs`, limited to the smallest or largest duration on overflow.
+	pub fn saturating_sub(self, rhs: DurationFlex) -> DurationFlex {
+		saturate(self.total_nanos() - rhs.total_nanos())
+	}
+
+	/// `self * rhs`, limited to the smallest or largest duration on overflow.
+	pub fn saturating_mul(self, rhs: i64) -> DurationFlex {
+		match self.total_nanos().checked_mul(rhs as i128) {
+			Some(total) => saturate(total),
+			None if (self.total_nanos() < 0) == (rhs < 0) => saturate(MAX_NANOS),
+			None => saturate(MIN_NANOS),
+		}
+	}
+
+	/// `-self`, limited to the largest duration on overflow.
+	pub fn saturating_neg(self) -> DurationFlex {
+		saturate(-self.total_nanos())
+	}
+
+	/// `self + rhs`, wrapped around the range of durations on overflow, and whether it overflowed.
+	pub fn overflowing_add(self, rhs: DurationFlex) -> (DurationFlex, bool) {
+		overflowing(self.total_nanos() + rhs.total_nanos())
+	}
+
+	/// `self - rhs`, wrapped around the range of durations on overflow, and whether it overflowed.
+	pub fn overflowing_sub(self, rhs: DurationFlex) -> (DurationFlex, bool) {
+		overflowing(self.total_nanos() - rhs.total_nanos())
+	}
+
+	/// `self * rhs`, wrapped around the range of durations on overflow, and whether it overflowed.
+	pub fn overflowing_mul(self, rhs: i64) -> (DurationFlex, bool) {
+		match self.total_nanos().checked_mul(rhs as i128) {
+			Some(total) => overflowing(total),
+			None => {
+				// Multiplies modulo `RANGE` in two steps of 32 bits, so no intermediate value overflows.
+				let value = self.total_nanos().rem_euclid(RANGE as i128) as u128;
+				let (high, low) = ((rhs.unsigned_abs() >> 32) as u128, (rhs.unsigned_abs() & u32::MAX as u64) as u128);
+				let product = (value * high % RANGE * (1 << 32) % RANGE + value * low % RANGE) % RANGE;
+				let product = if rhs < 0 { (RANGE - product) % RANGE } else { product };
+
+				(wrap(product), true)
+			},
+		}
+	}
+
+	/// `-self`, wrapped around the range of durations on overflow, and whether it overflowed.
+	pub fn overflowing_neg(self) -> (DurationFlex, bool) {
+		overflowing(-self.total_nanos())
+	}
+
+	/// `self * rhs`, rounded to the nearest nano-second (half away from zero).
+	///
+	/// # Panics
+	///
+	/// If the result is NaN, infinite or doesn't fit, use [`DurationFlex::try_mul_f64`] to avoid it.
+	pub fn mul_f64(self, rhs: f64) -> DurationFlex {
+		self.try_mul_f64(rhs).unwrap()
+	}
+
+	/// `self * rhs`, rounded to the nearest nano-second (half away from zero), e.g. `1m30s` for `1m` and `1.5`.
+	pub fn try_mul_f64(self, rhs: f64) -> Result<DurationFlex, DurationFlexError> {
+		scale_f64(self, |value| value * rhs)
+	}
+
+	/// `self / rhs`, rounded to the nearest nano-second (half away from zero).
+	///
+	/// # Panics
+	///
+	/// If the result is NaN, infinite (like when `rhs` is zero) or doesn't fit, use [`DurationFlex::try_div_f64`] to
+	/// avoid it.
+	pub fn div_f64(self, rhs: f64) -> DurationFlex {
+		self.try_div_f64(rhs).unwrap()
+	}
+
+	/// `self / rhs`, rounded to the nearest nano-second (half away from zero), e.g. `40s` for `1m` and `1.5`.
+	pub fn try_div_f64(self, rhs: f64) -> Result<DurationFlex, DurationFlexError> {
+		scale_f64(self, |value| value / rhs)
+	}
+
+	/// Ratio between `self` and `rhs`, e.g. `0.25` for `15m` and `1h`. Like any float division, it is infinite or NaN
+	/// when `rhs` is zero, and it is only as precise as an `f64`, so `200d1ns` and `200d` have a ratio of `1.0`.
+	pub fn div_duration_f64(self, rhs: DurationFlex) -> f64 {
+		self.total_nanos() as f64 / rhs.total_nanos() as f64
+	}
+}
+
+/// `op` applied to `value`, rounded to the nearest nano-second (half away from zero).
+///
+/// An `f64` only has 53 bits of precision, not enough for the nano-seconds of durations longer than ~104 days, so the
+/// seconds and nano-seconds are scaled separately and only the fraction of the seconds is rounded.
+fn scale_f64(value: DurationFlex, op: impl Fn(f64) -> f64) -> Result<DurationFlex, DurationFlexError> {
+	let total = op(value.total_nanos() as f64);
+	match total {
+		_ if total.is_nan() => Err(DurationFlexError::NotANumber),
+		_ if total.is_infinite() => Err(DurationFlexError::Infinite),
+		_ if total.abs() > MAX_NANOS as f64 => Err(DurationFlexError::OutOfRange),
+		_ => {
+			let secs = op(value.secs as f64);
+			let whole = secs.trunc();
+			let nanos = ((secs - whole) * NANOS_PER_SEC as f64 + op(value.nanos as f64)).round();
+
+			DurationFlex::from_total_nanos(whole as i128 * NANOS_PER_SEC as i128 + nanos as i128)
+				.ok_or(DurationFlexError::OutOfRange)
+		},
+	}
+}
+
+/// Duration with `total` nano-seconds, limited to the range of durations.
+fn saturate(total: i128) -> DurationFlex {
+	DurationFlex::from_total_nanos(total.clamp(MIN_NANOS, MAX_NANOS)).unwrap()
+}
+
+/// Duration with `total` nano-seconds wrapped around the range of durations, and whether it was out of it.
+fn overflowing(total: i128) -> (DurationFlex, bool) {
+	match DurationFlex::from_total_nanos(total) {
+		Some(value) => (value, false),
+		None => (wrap(total.rem_euclid(RANGE as i128) as u128), true),
+	}
+}
+
+/// Duration congruent to `value` nano-seconds, modulo `RANGE`.
+fn wrap(value: u128) -> DurationFlex {
+	let value = (value % RANGE) as i128;
+	let total = if value > MAX_NANOS { value - RANGE as i128 } else { value };
+
+	DurationFlex::from_total_nanos(total).unwrap()
+}
+
+impl Add for DurationFlex {
+	type Output = DurationFlex;
+
+	fn add(self, rhs: DurationFlex) -> Self::Output {
+		self.checked_add(rhs).expect("overflow when adding durations")
+	}
+}
+
+impl Sub for DurationFlex {
+	type Output = DurationFlex;
+
+	fn sub(self, rhs: DurationFlex) -> Self::Output {
+		self.checked_sub(rhs).expect("overflow when subtracting durations")
+	}
+}
+
+impl Add<Duration> for DurationFlex {
+	type Output = DurationFlex;
+
+	fn add(self, rhs: Duration) -> Self::Output {
+		self + DurationFlex::from(rhs)
+	}
+}
+
+impl Sub<Duration> for DurationFlex {
+	type Output = DurationFlex;
+
+	fn sub(self, rhs: Duration) -> Self::Output {
+		self - DurationFlex::from(rhs)
+	}
+}
+
+impl Mul<i64> for DurationFlex {
+	type Output = DurationFlex;
+
+	fn mul(self, rhs: i64) -> Self::Output {
+		self.checked_mul(rhs).expect("overflow when multiplying duration by scalar")
+	}
+}
+
+impl Mul<DurationFlex> for i64 {
+	type Output = DurationFlex;
+
+	fn mul(self, rhs: DurationFlex) -> Self::Output {
+		rhs * self
+	}
+}
+
+impl Div<i64> for DurationFlex {
+	type Output = DurationFlex;
+
+	fn div(self, rhs: i64) -> Self::Output {
+		assert_ne!(rhs, 0, "divide by zero error when dividing duration by scalar");
+		self.checked_div(rhs).expect("overflow when dividing duration by scalar")
+	}
+}
+
+impl Rem for DurationFlex {
+	type Output = DurationFlex;
+
+	fn rem(self, rhs: DurationFlex) -> Self::Output {
+		self.checked_rem(rhs).expect("divide by zero error when calculating the remainder of durations")
+	}
+}
+
+impl Neg for DurationFlex {
+	type Output = DurationFlex;
+
+	fn neg(self) -> Self::Output {
+		self.checked_neg().expect("overflow when negating duration")
+	}
+}
+
+impl AddAssign for DurationFlex {
+	fn add_assign(&mut self, rhs: DurationFlex) {
+		*self = *self + rhs;
+	}
+}
+
+impl SubAssign for DurationFlex {
+	fn sub_assign(&mut self, rhs: DurationFlex) {
+		*self = *self - rhs;
+	}
+}
+
+impl AddAssign<Duration> for DurationFlex {
+	fn add_assign(&mut self, rhs: Duration) {
+		*self = *self + rhs;
+	}
+}
+
+impl SubAssign<Duration> for DurationFlex {
+	fn sub_assign(&mut self, rhs: Duration) {
+		*self = *self - rhs;
+	}
+}
+
+impl MulAssign<i64> for DurationFlex {
+	fn mul_assign(&mut self, rhs: i64) {
+		*self = *self * rhs;
+	}
+}
+
+impl DivAssign<i64> for DurationFlex {
+	fn div_assign(&mut self, rhs: i64) {
+		*self = *self / rhs;
+	}
+}
+
+impl RemAssign for DurationFlex {
+	fn rem_assign(&mut self, rhs: DurationFlex) {
+		*self = *self % rhs;
+	}
+}
+
+/// # Panics
+///
+/// Panics on overflow, use [`DurationFlex::checked_add`] to avoid it.
+impl Sum for DurationFlex {
+	fn sum<I: Iterator<Item = DurationFlex>>(iter: I) -> Self {
+		iter.fold(DurationFlex::ZERO, |total, value| total + value)
+	}
+}
+
+/// # Panics
+///
+/// Panics on overflow, use [`DurationFlex::checked_add`] to avoid it.
+impl<'a> Sum<&'a DurationFlex> for DurationFlex {
+	fn sum<I: Iterator<Item = &'a DurationFlex>>(iter: I) -> Self {
+		iter.copied().sum()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use chrono::Duration;
+
+	use crate::{flex, DurationFlex, DurationFlexError};
+
+	const MAX: DurationFlex = DurationFlex::MAX;
+	const MIN: DurationFlex = DurationFlex::MIN;
+
+	#[test]
+	fn operators() {
+		assert_eq!(flex("1h") + flex("30m"), flex("1h30m"));
+		assert_eq!(flex("1h") - flex("1h30m"), flex("-30m"));
+		assert_eq!(flex("1s500ms") + flex("-2s"), flex("-500ms"));
+		assert_eq!(flex("1h") + Duration::try_minutes(30).unwrap(), flex("1h30m"));
+		assert_eq!(flex("1h") - Duration::try_minutes(90).unwrap(), flex("-30m"));
+		assert_eq!(flex("1m30s") * 3, flex("4m30s"));
+		assert_eq!(3 * flex("1m30s"), flex("4m30s"));
+		assert_eq!(flex("1m30s") * -2, flex("-3m"));
+		assert_eq!(flex("4m30s") / 3, flex("1m30s"));
+		assert_eq!(flex("1s") / 3, flex("333ms333us333ns"));
+		assert_eq!(flex("-1s") / 3, flex("-333ms333us333ns"));
+		assert_eq!(flex("1h") % flex("7m"), flex("4m"));
+		assert_eq!(flex("-1h") % flex("7m"), flex("-4m"));
+		assert_eq!(-flex("1h30m"), flex("-1h30m"));
+		assert_eq!(-flex("-1ns"), flex("1ns"));
+		assert_eq!(-MAX, DurationFlex { secs: -i64::MAX, nanos: -999_999_999 });
+	}
+
+	#[test]
+	fn assign() {
+		let mut value = flex("1h");
+		value += flex("30m");
+		assert_eq!(value, flex("1h30m"));
+		value -= flex("1h");
+		assert_eq!(value, flex("30m"));
+		value += Duration::try_seconds(30).unwrap();
+		assert_eq!(value, flex("30m30s"));
+		value -= Duration::try_minutes(30).unwrap();
+		assert_eq!(value, flex("30s"));
+		value *= 4;
+		assert_eq!(value, flex("2m"));
+		value /= 8;
+		assert_eq!(value, flex("15s"));
+		value %= flex("4s");
+		assert_eq!(value, flex("3s"));
+	}
+
+	#[test]
+	fn sum() {
+		let values = [flex("1h"), flex("30m"), flex("-5s")];
+		assert_eq!(values.iter().sum::<DurationFlex>(), flex("1h29m55s"));
+		assert_eq!(values.into_iter().sum::<DurationFlex>(), flex("1h29m55s"));
+		assert_eq!(Vec::<DurationFlex>::new().into_iter().sum::<DurationFlex>(), flex("0s"));
+	}
+
+	#[test]
+	#[should_panic(expected = "overflow when adding durations")]
+	fn add_overflow() {
+		let _ = MAX + flex("1ns");
+	}
+
+	#[test]
+	#[should_panic(expected = "overflow when negating duration")]
+	fn neg_overflow() {
+		let _ = -MIN;
+	}
+
+	#[test]
+	#[should_panic(expected = "divide by zero error when dividing duration by scalar")]
+	fn div_zero() {
+		let _ = flex("1s") / 0;
+	}
+
+	#[test]
+	fn checked() {
+		assert_eq!(flex("1h").checked_add(flex("1m")), Some(flex("1h1m")));
+		assert_eq!(MAX.checked_add(flex("1ns")), None);
+		assert_eq!(MIN.checked_sub(flex("1ns")), None);
+		assert_eq!(MIN.checked_sub(flex("-1ns")), Some(DurationFlex { secs: i64::MIN, nanos: -999_999_998 }));
+		assert_eq!(flex("1h").checked_mul(2), Some(flex("2h")));
+		assert_eq!(MAX.checked_mul(2), None);
+		assert_eq!(MAX.checked_mul(i64::MIN), None);
+		assert_eq!(flex("1h").checked_div(2), Some(flex("30m")));
+		assert_eq!(flex("1h").checked_div(0), None);
+		assert_eq!(MIN.checked_div(-1), None);
+		assert_eq!(flex("1h").checked_rem(flex("7m")), Some(flex("4m")));
+		assert_eq!(flex("1h").checked_rem(flex("0s")), None);
+		assert_eq!(MIN.checked_neg(), None);
+		assert_eq!(MAX.checked_neg(), Some(DurationFlex { secs: -i64::MAX, nanos: -999_999_999 }));
+	}
+
+	#[test]
+	fn saturating() {
+		assert_eq!(flex("1h").saturating_add(flex("1m")), flex("1h1m"));
+		assert_eq!(MAX.saturating_add(flex("1h")), MAX);
+		assert_eq!(MIN.saturating_sub(flex("1h")), MIN);
+		assert_eq!(MIN.saturating_add(MIN), MIN);
+		assert_eq!(flex("1h").saturating_mul(3), flex("3h"));
+		assert_eq!(MAX.saturating_mul(2), MAX);
+		assert_eq!(MAX.saturating_mul(-2), MIN);
+		assert_eq!(MIN.saturating_mul(i64::MIN), MAX);
+		assert_eq!(MIN.saturating_mul(i64::MAX), MIN);
+		assert_eq!(MIN.saturating_neg(), MAX);
+	}
+
+	#[test]
+	fn overflowing() {
+		assert_eq!(flex("1h").overflowing_add(flex("1m")), (flex("1h1m"), false));
+		assert_eq!(MAX.overflowing_add(flex("1ns")), (MIN, true));
+		assert_eq!(MIN.overflowing_sub(flex("1ns")), (MAX, true));
+		assert_eq!(MAX.overflowing_add(MAX), (MAX + MIN - flex("1ns"), true));
+		assert_eq!(flex("1h").overflowing_mul(-3), (flex("-3h"), false));
+		assert_eq!(MAX.overflowing_mul(2), (MAX.overflowing_add(MAX).0, true));
+		assert_eq!(MAX.overflowing_mul(-2), (MAX.overflowing_add(MAX).0.overflowing_neg().0, true));
+		assert_eq!(MIN.overflowing_neg(), (DurationFlex { secs: i64::MIN, nanos: 0 }, true));
+		assert_eq!(MAX.overflowing_neg(), (-MAX, false));
+
+		// Multiplying by `n` wraps just like adding `n` times.
+		for factor in [7, 11, 1 << 20] {
+			let value = DurationFlex { secs: i64::MAX / 5, nanos: 123_456_789 };
+			let expected = (1..factor).fold(value, |total, _| total.overflowing_add(value).0);
+			assert_eq!(value.overflowing_mul(factor), (expected, true), "{}", factor);
+		}
+
+		let large = DurationFlex { secs: 1 << 40, nanos: 987_654_321 };
+		let expected = large.overflowing_mul(i64::MAX / 3).0.overflowing_mul(3).0;
+		assert_eq!(large.overflowing_mul(i64::MAX / 3 * 3).0, expected);
+	}
+
+	#[test]
+	fn floats() {
+		assert_eq!(flex("1m").mul_f64(1.5), flex("1m30s"));
+		assert_eq!(flex("1m").mul_f64(-0.5), flex("-30s"));
+		assert_eq!(flex("1s").mul_f64(1e-9 / 2.0), flex("1ns"));
+		assert_eq!(flex("1s").mul_f64(-1e-9 / 2.0), flex("-1ns"));
+		assert_eq!(flex("1m").div_f64(1.5), flex("40s"));
+		assert_eq!(flex("1s").div_f64(3.0), flex("333ms333us333ns"));
+		assert_eq!(flex("2s").div_f64(3.0), flex("666ms666us667ns"));
+		assert_eq!(flex("15m").div_duration_f64(flex("1h")), 0.25);
+		assert_eq!(flex("-1h").div_duration_f64(flex("15m")), -4.0);
+		assert!(flex("1h").div_duration_f64(flex("0s")).is_infinite());
+		assert_eq!(flex("200d1ns").div_duration_f64(flex("200d")), 1.0);
+
+		assert_eq!(flex("200d1ns").mul_f64(1.0), flex("200d1ns"));
+		assert_eq!(flex("-200d1ns").div_f64(-1.0), flex("200d1ns"));
+		assert_eq!(flex("1000w1ns").mul_f64(2.0), flex("2000w2ns"));
+		assert_eq!(flex("1000w1s").mul_f64(0.5), flex("500w500ms"));
+		assert_eq!(flex("1000w3ns").div_f64(2.0), flex("500w2ns"));
+
+		assert_eq!(flex("1s").try_mul_f64(f64::NAN), Err(DurationFlexError::NotANumber));
+		assert_eq!(flex("1s").try_mul_f64(f64::INFINITY), Err(DurationFlexError::Infinite));
+		assert_eq!(flex("1s").try_mul_f64(1e30), Err(DurationFlexError::OutOfRange));
+		assert_eq!(MAX.try_mul_f64(2.0), Err(DurationFlexError::OutOfRange));
+		assert_eq!(flex("1s").try_div_f64(0.0), Err(DurationFlexError::Infinite));
+		assert_eq!(flex("0s").try_div_f64(0.0), Err(DurationFlexError::NotANumber));
+	}
+
+	#[test]
+	fn secs_f64() {
+		assert_eq!(flex("1s500ms").as_secs_f64(), 1.5);
+		assert_eq!(flex("-1s500ms").as_secs_f64(), -1.5);
+		assert_eq!(flex("1ns").as_secs_f64(), 1e-9);
+		assert_eq!(DurationFlex::from_secs_f64(1.5), flex("1s500ms"));
+		assert_eq!(DurationFlex::from_secs_f64(-0.000_000_000_5), flex("-1ns"));
+		assert_eq!(DurationFlex::from_secs_f64(90061.25), flex("1d1h1m1s250ms"));
+		assert_eq!(DurationFlex::try_from_secs_f64(f64::NAN), Err(DurationFlexError::NotANumber));
+		assert_eq!(DurationFlex::try_from_secs_f64(f64::NEG_INFINITY), Err(DurationFlexError::Infinite));
+		assert_eq!(DurationFlex::try_from_secs_f64(1e19), Err(DurationFlexError::OutOfRange));
+
+		for value in ["1h30m", "-2d3ms", "1w2d3h4m5s6ms7us8ns"] {
+			assert_eq!(DurationFlex::from_secs_f64(flex(value).as_secs_f64()), flex(value));
+		}
+	}
+}
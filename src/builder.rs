@@ -0,0 +1,108 @@
+use crate::unit::Unit;
+use crate::{DurationFlex, DurationFlexError};
+
+/// Builds a [`DurationFlex`] one unit at a time, see [`DurationFlex::builder`].
+///
+/// Each unit can have any amount, including negative ones, and they are summed by [`Builder::build`], e.g. 1 hour and
+/// -30 minutes are `30m`. Setting the same unit again replaces its amount.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Builder {
+	amounts: [i64; Unit::ALL.len()],
+}
+
+impl Builder {
+	/// Amount of `unit`, e.g. `amount(Unit::Hour, 1)` is the same as `hours(1)`.
+	pub fn amount(mut self, unit: Unit, value: i64) -> Self {
+		self.amounts[unit as usize] = value;
+		self
+	}
+
+	/// Amount of weeks.
+	pub fn weeks(self, value: i64) -> Self {
+		self.amount(Unit::Week, value)
+	}
+
+	/// Amount of days.
+	pub fn days(self, value: i64) -> Self {
+		self.amount(Unit::Day, value)
+	}
+
+	/// Amount of hours.
+	pub fn hours(self, value: i64) -> Self {
+		self.amount(Unit::Hour, value)
+	}
+
+	/// Amount of minutes.
+	pub fn minutes(self, value: i64) -> Self {
+		self.amount(Unit::Minute, value)
+	}
+
+	/// Amount of seconds.
+	pub fn seconds(self, value: i64) -> Self {
+		self.amount(Unit::Second, value)
+	}
+
+	/// Amount of milli-seconds.
+	pub fn milliseconds(self, value: i64) -> Self {
+		self.amount(Unit::Millisecond, value)
+	}
+
+	/// Amount of micro-seconds.
+	pub fn microseconds(self, value: i64) -> Self {
+		self.amount(Unit::Microsecond, value)
+	}
+
+	/// Amount of nano-seconds.
+	pub fn nanoseconds(self, value: i64) -> Self {
+		self.amount(Unit::Nanosecond, value)
+	}
+
+	/// Sums all the units, or fails with [`DurationFlexError::OutOfRange`] if the result doesn't fit.
+	pub fn build(&self) -> Result<DurationFlex, DurationFlexError> {
+		let total = Unit::ALL.into_iter().map(|unit| self.amounts[unit as usize] as i128 * unit.nanos() as i128).sum();
+
+		DurationFlex::from_total_nanos(total).ok_or(DurationFlexError::OutOfRange)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{flex, DurationFlex, DurationFlexError, Unit};
+
+	#[test]
+	fn build() {
+		assert_eq!(DurationFlex::builder().build(), Ok(DurationFlex::ZERO));
+		assert_eq!(DurationFlex::builder().hours(1).minutes(30).build(), Ok(flex("1h30m")));
+		assert_eq!(
+			DurationFlex::builder()
+				.weeks(1)
+				.days(2)
+				.hours(3)
+				.minutes(4)
+				.seconds(5)
+				.milliseconds(6)
+				.microseconds(7)
+				.nanoseconds(8)
+				.build(),
+			Ok(flex("1w2d3h4m5s6ms7us8ns"))
+		);
+		assert_eq!(DurationFlex::builder().minutes(90).seconds(90).build(), Ok(flex("1h31m30s")));
+		assert_eq!(DurationFlex::builder().hours(1).minutes(-30).build(), Ok(flex("30m")));
+		assert_eq!(DurationFlex::builder().hours(-1).build(), Ok(flex("-1h")));
+		assert_eq!(DurationFlex::builder().hours(1).hours(2).build(), Ok(flex("2h")));
+		assert_eq!(DurationFlex::builder().amount(Unit::Day, 3).build(), Ok(flex("3d")));
+	}
+
+	#[test]
+	fn build_errors() {
+		assert_eq!(DurationFlex::builder().weeks(i64::MAX).build(), Err(DurationFlexError::OutOfRange));
+		assert_eq!(
+			DurationFlex::builder().seconds(i64::MAX).milliseconds(1_000).build(),
+			Err(DurationFlexError::OutOfRange)
+		);
+		assert_eq!(
+			DurationFlex::builder().seconds(i64::MAX).milliseconds(999).build(),
+			Ok(DurationFlex::new(i64::MAX, 999_000_000))
+		);
+	}
+}
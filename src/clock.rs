@@ -0,0 +1,260 @@
+use std::fmt::{Display, Formatter};
+
+use crate::unit::Unit;
+use crate::{DurationFlex, DurationFlexError, Grammar, NANOS_PER_SEC};
+
+/// Formats a [`DurationFlex`] like a clock, e.g. `01:30:00`, see [`DurationFlex::clock`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Clock {
+	value: DurationFlex,
+	pad_hours: bool,
+	precision: Option<usize>,
+	days: bool,
+}
+
+impl Clock {
+	pub(crate) fn new(value: DurationFlex) -> Self {
+		Clock { value, pad_hours: true, precision: None, days: false }
+	}
+
+	/// Pad the hours to 2 digits, e.g. `01:30:00` instead of `1:30:00`. Enabled by default.
+	pub fn pad_hours(mut self, value: bool) -> Self {
+		self.pad_hours = value;
+		self
+	}
+
+	/// Exact amount of digits (at most 9) of the fraction of seconds, truncated, e.g. `00:00:01.500` with 3. By
+	/// default, only the digits needed are shown, e.g. `00:00:01.5`.
+	pub fn precision(mut self, value: usize) -> Self {
+		self.precision = Some(value.min(9));
+		self
+	}
+
+	/// Show whole days apart, like .NET's `TimeSpan`, e.g. `1.02:00:00` instead of `26:00:00`.
+	pub fn days(mut self, value: bool) -> Self {
+		self.days = value;
+		self
+	}
+}
+
+impl Display for Clock {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let total = self.value.total_nanos();
+		if total < 0 {
+			f.write_str("-")?;
+		}
+
+		let total = total.unsigned_abs();
+		let mut hours = total / Unit::Hour.nanos() as u128;
+		let minutes = total % Unit::Hour.nanos() as u128 / Unit::Minute.nanos() as u128;
+		let secs = total % Unit::Minute.nanos() as u128 / NANOS_PER_SEC as u128;
+		let nanos = total % NANOS_PER_SEC as u128;
+
+		if self.days && hours >= 24 {
+			write!(f, "{}.", hours / 24)?;
+			hours %= 24;
+		}
+
+		if self.pad_hours {
+			write!(f, "{:02}:{:02}:{:02}", hours, minutes, secs)?;
+		} else {
+			write!(f, "{}:{:02}:{:02}", hours, minutes, secs)?;
+		}
+
+		let fraction = format!("{:09}", nanos);
+		match self.precision {
+			None if nanos > 0 => write!(f, ".{}", fraction.trim_end_matches('0')),
+			Some(precision) if precision > 0 => write!(f, ".{}", &fraction[..precision]),
+			_ => Ok(()),
+		}
+	}
+}
+
+/// Parses the clock format, `[-][d.]h:mm[:ss[.fraction]]`, e.g. `01:30:00`, `1:30` or `2.04:00:00`.
+///
+/// Hours can be larger than a day, unless days are specified. Minutes and seconds must have 2 digits and be smaller
+/// than 60. The fraction of seconds can have any amount of digits, but is truncated to nano-seconds.
+pub(crate) fn parse(input: &str) -> Result<DurationFlex, DurationFlexError> {
+	let garbage = |offset: usize| {
+		DurationFlexError::TrailingGarbage {
+			offset,
+			text: input[offset..].to_string(),
+			suggestion: None,
+			grammar: Grammar::Clock,
+		}
+	};
+	let digits_end =
+		|start: usize| start + input[start..].find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len() - start);
+
+	let (negative, start) = match input.as_bytes().first() {
+		Some(b'-') => (true, 1),
+		Some(b'+') => (false, 1),
+		_ => (false, 0),
+	};
+
+	if start == input.len() {
+		return Err(DurationFlexError::EmptyInput { grammar: Grammar::Clock });
+	}
+
+	// `[d.]h`
+	let first_end = digits_end(start);
+	if first_end == start {
+		return Err(garbage(start));
+	}
+
+	let (days, hours) = match input.as_bytes().get(first_end) {
+		Some(b'.') => {
+			let hours_end = digits_end(first_end + 1);
+			if hours_end == first_end + 1 {
+				return Err(garbage(first_end + 1));
+			}
+
+			(Some(start..first_end), first_end + 1..hours_end)
+		},
+		_ => (None, start..first_end),
+	};
+
+	// `:mm[:ss[.fraction]]`
+	let two_digits = |offset: usize| {
+		match input.as_bytes().get(offset) {
+			Some(b':') if digits_end(offset + 1) == offset + 3 => Some(offset + 1..offset + 3),
+			_ => None,
+		}
+	};
+
+	let minutes = two_digits(hours.end).ok_or_else(|| garbage(hours.end))?;
+	let seconds = two_digits(minutes.end);
+	let mut offset = seconds.as_ref().map_or(minutes.end, |seconds| seconds.end);
+
+	let mut fraction = "";
+	if seconds.is_some() && input.as_bytes().get(offset) == Some(&b'.') {
+		let end = digits_end(offset + 1);
+		if end == offset + 1 {
+			return Err(garbage(offset));
+		}
+
+		fraction = &input[offset + 1..end];
+		offset = end;
+	}
+
+	if offset < input.len() {
+		return Err(garbage(offset));
+	}
+
+	let field =
+		|range: std::ops::Range<usize>, limit: u64| {
+			input[range.clone()].parse::<u64>().ok().filter(|value| *value < limit).ok_or_else(|| {
+				DurationFlexError::ComponentOverflow { offset: range.start, text: input[range].to_string() }
+			})
+		};
+
+	let hours = field(hours, if days.is_some() { 24 } else { u64::MAX })?;
+	let days = days.map(|days| field(days, u64::MAX)).transpose()?.unwrap_or(0);
+	let minutes = field(minutes, 60)?;
+	let seconds = seconds.map(|seconds| field(seconds, 60)).transpose()?.unwrap_or(0);
+
+	let fraction = &fraction[..fraction.len().min(9)];
+	let nanos = match fraction {
+		"" => 0,
+		_ => fraction.parse::<i128>().unwrap() * 10i128.pow(9 - fraction.len() as u32),
+	};
+
+	let total = days as i128 * Unit::Day.nanos() as i128
+		+ hours as i128 * Unit::Hour.nanos() as i128
+		+ minutes as i128 * Unit::Minute.nanos() as i128
+		+ seconds as i128 * NANOS_PER_SEC as i128
+		+ nanos;
+
+	DurationFlex::from_total_nanos(if negative { -total } else { total }).ok_or(DurationFlexError::OutOfRange)
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{flex, DurationFlex, DurationFlexError, Grammar};
+
+	#[test]
+	fn parse() {
+		assert_eq!(DurationFlex::parse_clock("01:30:00"), Ok(flex("1h30m")));
+		assert_eq!(DurationFlex::parse_clock("1:30"), Ok(flex("1h30m")));
+		assert_eq!(DurationFlex::parse_clock("2.04:00:00"), Ok(flex("2d4h")));
+		assert_eq!(DurationFlex::parse_clock("36:00:00"), Ok(flex("1d12h")));
+		assert_eq!(DurationFlex::parse_clock("00:00:01.5"), Ok(flex("1s500ms")));
+		assert_eq!(DurationFlex::parse_clock("00:00:00.1234567891"), Ok(flex("123ms456us789ns")));
+		assert_eq!(DurationFlex::parse_clock("-1.02:03:04.005"), Ok(flex("-1d2h3m4s5ms")));
+		assert_eq!(DurationFlex::parse_clock("+0:00"), Ok(flex("0s")));
+	}
+
+	#[test]
+	fn parse_errors() {
+		assert_eq!(DurationFlex::parse_clock(""), Err(DurationFlexError::EmptyInput { grammar: Grammar::Clock }));
+		assert_eq!(DurationFlex::parse_clock("-"), Err(DurationFlexError::EmptyInput { grammar: Grammar::Clock }));
+		assert_eq!(
+			DurationFlex::parse_clock("1:60"),
+			Err(DurationFlexError::ComponentOverflow { offset: 2, text: "60".to_string() })
+		);
+		assert_eq!(
+			DurationFlex::parse_clock("1.24:00:00"),
+			Err(DurationFlexError::ComponentOverflow { offset: 2, text: "24".to_string() })
+		);
+		assert_eq!(
+			DurationFlex::parse_clock("1:30x"),
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 4,
+				text: "x".to_string(),
+				suggestion: None,
+				grammar: Grammar::Clock
+			})
+		);
+
+		assert!(DurationFlex::parse_clock("1").is_err());
+		assert!(DurationFlex::parse_clock("1:3").is_err());
+		assert!(DurationFlex::parse_clock("1:30:5").is_err());
+		assert!(DurationFlex::parse_clock("1:30.5").is_err());
+		assert!(DurationFlex::parse_clock("1:30:00.").is_err());
+		assert!(DurationFlex::parse_clock("1.:30").is_err());
+		assert!(DurationFlex::parse_clock(":30").is_err());
+		assert!(DurationFlex::parse_clock("1h30m").is_err());
+		assert!(DurationFlex::parse_clock("99999999999999999999:00").is_err());
+	}
+
+	#[test]
+	fn help() {
+		let help = |input: &str| DurationFlex::parse_clock(input).unwrap_err().help();
+
+		assert_eq!(help("1:30x"), Some("the format is `[-][d.]hh:mm[:ss[.fffffffff]]`, like `01:30:00`"));
+		assert_eq!(help(""), Some("the format is `[-][d.]hh:mm[:ss[.fffffffff]]`, like `01:30:00`"));
+	}
+
+	#[test]
+	fn format() {
+		assert_eq!(flex("0s").clock().to_string(), "00:00:00");
+		assert_eq!(flex("1h30m").clock().to_string(), "01:30:00");
+		assert_eq!(flex("1h30m").clock().pad_hours(false).to_string(), "1:30:00");
+		assert_eq!(flex("1d2h").clock().to_string(), "26:00:00");
+		assert_eq!(flex("1d2h").clock().days(true).to_string(), "1.02:00:00");
+		assert_eq!(flex("2h").clock().days(true).to_string(), "02:00:00");
+		assert_eq!(flex("1s500ms").clock().to_string(), "00:00:01.5");
+		assert_eq!(flex("1s500ms").clock().precision(3).to_string(), "00:00:01.500");
+		assert_eq!(flex("1s999ms").clock().precision(1).to_string(), "00:00:01.9");
+		assert_eq!(flex("1s500ms").clock().precision(0).to_string(), "00:00:01");
+		assert_eq!(flex("1s1ns").clock().precision(12).to_string(), "00:00:01.000000001");
+		assert_eq!(flex("-1d2h3m4s5ms").clock().days(true).to_string(), "-1.02:03:04.005");
+
+		for value in ["1h30m", "-1d2h3m4s5ms", "1ns"] {
+			assert_eq!(DurationFlex::parse_clock(&flex(value).clock().to_string()), Ok(flex(value)));
+			assert_eq!(DurationFlex::parse_clock(&flex(value).clock().days(true).to_string()), Ok(flex(value)));
+		}
+	}
+
+	#[cfg(feature = "clock")]
+	#[test]
+	fn from_str() {
+		assert_eq!("1:30".parse::<DurationFlex>(), Ok(flex("1h30m")));
+		assert_eq!("1h30m".parse::<DurationFlex>(), Ok(flex("1h30m")));
+		assert_eq!(
+			"1:60".parse::<DurationFlex>(),
+			Err(DurationFlexError::ComponentOverflow { offset: 2, text: "60".to_string() })
+		);
+		assert!("1h30".parse::<DurationFlex>().is_err());
+	}
+}
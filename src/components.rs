@@ -0,0 +1,126 @@
+use crate::unit::Unit;
+use crate::DurationFlex;
+
+/// Breakdown of a [`DurationFlex`] in whole units, from weeks down to nano-seconds, see [`DurationFlex::components`].
+///
+/// Each component is smaller than one of the next larger unit, e.g. `1h30m` has 1 hour and 30 minutes, never 90
+/// minutes. All components have the sign of the duration.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Components {
+	/// Whole weeks.
+	pub weeks: i64,
+	/// Days, from -6 to 6.
+	pub days: i64,
+	/// Hours, from -23 to 23.
+	pub hours: i64,
+	/// Minutes, from -59 to 59.
+	pub minutes: i64,
+	/// Seconds, from -59 to 59.
+	pub seconds: i64,
+	/// Milli-seconds, from -999 to 999.
+	pub milliseconds: i64,
+	/// Micro-seconds, from -999 to 999.
+	pub microseconds: i64,
+	/// Nano-seconds, from -999 to 999.
+	pub nanoseconds: i64,
+}
+
+impl Components {
+	pub(crate) fn new(value: &DurationFlex) -> Self {
+		let total = value.total_nanos();
+		let mut remaining = total.unsigned_abs();
+		let mut amounts = [0i64; Unit::ALL.len()];
+		for unit in Unit::ALL {
+			let amount = (remaining / unit.nanos() as u128) as i64;
+			remaining %= unit.nanos() as u128;
+			amounts[unit as usize] = if total < 0 { -amount } else { amount };
+		}
+
+		let [weeks, days, hours, minutes, seconds, milliseconds, microseconds, nanoseconds] = amounts;
+		Components { weeks, days, hours, minutes, seconds, milliseconds, microseconds, nanoseconds }
+	}
+
+	/// Component of `unit`, e.g. `30` for [`Unit::Minute`] in `1h30m`.
+	pub fn get(&self, unit: Unit) -> i64 {
+		match unit {
+			Unit::Week => self.weeks,
+			Unit::Day => self.days,
+			Unit::Hour => self.hours,
+			Unit::Minute => self.minutes,
+			Unit::Second => self.seconds,
+			Unit::Millisecond => self.milliseconds,
+			Unit::Microsecond => self.microseconds,
+			Unit::Nanosecond => self.nanoseconds,
+		}
+	}
+
+	/// All components, from weeks down to nano-seconds, including the zero ones.
+	pub fn iter(&self) -> impl Iterator<Item = (Unit, i64)> + '_ {
+		Unit::ALL.into_iter().map(|unit| (unit, self.get(unit)))
+	}
+}
+
+impl DurationFlex {
+	/// Breakdown in whole units, from weeks down to nano-seconds:
+	/// ```
+	/// use duration_flex::{DurationFlex, Unit};
+	///
+	/// # pub fn main() {
+	/// let components = DurationFlex::try_from("1d2h30m").unwrap().components();
+	/// assert_eq!((components.days, components.hours, components.minutes), (1, 2, 30));
+	/// assert_eq!(components.get(Unit::Second), 0);
+	/// # }
+	/// ```
+	pub fn components(&self) -> Components {
+		Components::new(self)
+	}
+
+	/// Total amount of whole `unit`s, truncated towards zero, e.g. `90` minutes in `1h30m`.
+	pub fn total_in(&self, unit: Unit) -> i128 {
+		self.total_nanos() / unit.nanos() as i128
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{flex, Components, DurationFlex, Unit};
+
+	#[test]
+	fn components() {
+		assert_eq!(
+			flex("1w2d3h4m5s6ms7us8ns").components(),
+			Components {
+				weeks: 1,
+				days: 2,
+				hours: 3,
+				minutes: 4,
+				seconds: 5,
+				milliseconds: 6,
+				microseconds: 7,
+				nanoseconds: 8
+			}
+		);
+		assert_eq!(flex("0s").components(), Components::default());
+		assert_eq!(flex("-1h30m").components(), Components { hours: -1, minutes: -30, ..Components::default() });
+
+		assert_eq!(DurationFlex::MAX.components().weeks, i64::MAX / 604_800);
+		assert_eq!(DurationFlex::MAX.components().nanoseconds, 999);
+	}
+
+	#[test]
+	fn iter() {
+		let components: Vec<_> = flex("1h30m").components().iter().filter(|(_, amount)| *amount != 0).collect();
+		assert_eq!(components, [(Unit::Hour, 1), (Unit::Minute, 30)]);
+		assert_eq!(flex("1s").components().iter().count(), 8);
+	}
+
+	#[test]
+	fn total_in() {
+		assert_eq!(flex("1h30m").total_in(Unit::Minute), 90);
+		assert_eq!(flex("1h30m").total_in(Unit::Hour), 1);
+		assert_eq!(flex("-1h30m").total_in(Unit::Second), -5400);
+		assert_eq!(flex("6d23h").total_in(Unit::Week), 0);
+
+		assert_eq!(DurationFlex::MIN.total_in(Unit::Nanosecond), i64::MIN as i128 * 1_000_000_000 - 999_999_999);
+	}
+}
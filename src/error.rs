@@ -0,0 +1,375 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// Errors returned by the different methods.
+///
+/// Parsing errors carry the byte offset in the input where the problem was found, alongside the offending text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DurationFlexError {
+	/// String format is not valid.
+	InvalidFormat,
+
+	/// Value is out of range.
+	OutOfRange,
+
+	/// Floating point value is NaN, e.g. `f64::NAN` seconds.
+	NotANumber,
+
+	/// Floating point value is infinite, e.g. `f64::INFINITY` seconds, or a division by zero.
+	Infinite,
+
+	/// Rounding quantum is zero or negative, e.g. [`DurationFlex::round_to`](crate::DurationFlex::round_to) with `0s`.
+	InvalidQuantum,
+
+	/// Input has no components, e.g. `""` or `"-"`.
+	EmptyInput {
+		/// Grammar of the parser, see [`DurationFlexError::help`].
+		grammar: Grammar,
+	},
+
+	/// Component has a unit that is not supported, e.g. `1y` (`y` is not supported).
+	UnknownUnit {
+		/// Byte offset of the unit in the input.
+		offset: usize,
+		/// The unit, as written in the input.
+		unit: String,
+		/// Corrected input, if it could be guessed, see [`DurationFlexError::suggestion`].
+		suggestion: Option<String>,
+		/// Grammar of the parser, see [`DurationFlexError::help`].
+		grammar: Grammar,
+	},
+
+	/// Component is not in the expected order (from the largest unit down to the smallest), e.g. `5s5d`.
+	UnitOutOfOrder {
+		/// Byte offset of the unit in the input.
+		offset: usize,
+		/// The unit, as written in the input.
+		unit: String,
+		/// Corrected input, if it could be guessed, see [`DurationFlexError::suggestion`].
+		suggestion: Option<String>,
+		/// Grammar of the parser, see [`DurationFlexError::help`].
+		grammar: Grammar,
+	},
+
+	/// Component unit was already specified, e.g. `1h1h`.
+	DuplicatedUnit {
+		/// Byte offset of the unit in the input.
+		offset: usize,
+		/// The unit, as written in the input.
+		unit: String,
+		/// Corrected input, if it could be guessed, see [`DurationFlexError::suggestion`].
+		suggestion: Option<String>,
+	},
+
+	/// Input that is not a component, like a number without unit, e.g. `1h30`.
+	TrailingGarbage {
+		/// Byte offset of the first character that couldn't be parsed.
+		offset: usize,
+		/// The remaining of the input, starting at `offset`.
+		text: String,
+		/// Corrected input, if it could be guessed, see [`DurationFlexError::suggestion`].
+		suggestion: Option<String>,
+		/// Grammar of the parser, see [`DurationFlexError::help`].
+		grammar: Grammar,
+	},
+
+	/// Component value doesn't fit in a duration, e.g. `99999999999999999999s`.
+	ComponentOverflow {
+		/// Byte offset of the component in the input.
+		offset: usize,
+		/// The component, as written in the input.
+		text: String,
+	},
+
+	/// Component has a unit without a fixed length, like years or months in ISO 8601, e.g. `P1Y`.
+	VariableLengthUnit {
+		/// Byte offset of the unit in the input.
+		offset: usize,
+		/// The unit, as written in the input.
+		unit: String,
+	},
+}
+
+/// Grammar of the parser that rejected an input, which selects the syntax described by [`DurationFlexError::help`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Grammar {
+	/// The `1w2d3h4m5s6ms7us8ns` format, see [`DurationFlex::try_from`](crate::DurationFlex::try_from) and
+	/// [`ParseOptions`](crate::ParseOptions).
+	Native,
+	/// See [`DurationFlex::parse_clock`](crate::DurationFlex::parse_clock).
+	Clock,
+	/// See [`DurationFlex::parse_go`](crate::DurationFlex::parse_go).
+	Go,
+	/// See [`DurationFlex::parse_iso8601`](crate::DurationFlex::parse_iso8601).
+	Iso8601,
+	/// See [`DurationFlex::parse_postgres`](crate::DurationFlex::parse_postgres).
+	Postgres,
+	/// See [`DurationFlex::parse_prometheus`](crate::DurationFlex::parse_prometheus).
+	Prometheus,
+	/// See [`DurationFlex::parse_systemd`](crate::DurationFlex::parse_systemd).
+	Systemd,
+}
+
+impl DurationFlexError {
+	/// Byte range, in the parsed input, of the text that caused the error.
+	pub fn span(&self) -> Option<Range<usize>> {
+		match self {
+			DurationFlexError::UnknownUnit { offset, unit, .. }
+			| DurationFlexError::UnitOutOfOrder { offset, unit, .. }
+			| DurationFlexError::DuplicatedUnit { offset, unit, .. }
+			| DurationFlexError::VariableLengthUnit { offset, unit } => Some(*offset..*offset + unit.len()),
+			DurationFlexError::TrailingGarbage { offset, text, .. }
+			| DurationFlexError::ComponentOverflow { offset, text } => Some(*offset..*offset + text.len()),
+			_ => None,
+		}
+	}
+
+	/// Corrected, canonical, version of the input, when the error looks like a common mistake. For instance, long
+	/// unit names (`5min` becomes `5m`), wrong case (`2D` becomes `2d`), wrong order (`5s5d` becomes `5d5s`) or
+	/// whitespace (`1h 30m` becomes `1h30m`).
+	pub fn suggestion(&self) -> Option<&str> {
+		match self {
+			DurationFlexError::UnknownUnit { suggestion, .. }
+			| DurationFlexError::UnitOutOfOrder { suggestion, .. }
+			| DurationFlexError::DuplicatedUnit { suggestion, .. }
+			| DurationFlexError::TrailingGarbage { suggestion, .. } => suggestion.as_deref(),
+			_ => None,
+		}
+	}
+
+	/// Replaces the suggestion, for the variants that have one.
+	pub(crate) fn with_suggestion(mut self, value: Option<String>) -> Self {
+		match &mut self {
+			DurationFlexError::UnknownUnit { suggestion, .. }
+			| DurationFlexError::UnitOutOfOrder { suggestion, .. }
+			| DurationFlexError::DuplicatedUnit { suggestion, .. }
+			| DurationFlexError::TrailingGarbage { suggestion, .. } => *suggestion = value,
+			_ => {},
+		}
+
+		self
+	}
+
+	/// Short description of the text pointed by [`DurationFlexError::span`].
+	pub fn label(&self) -> Option<&'static str> {
+		match self {
+			DurationFlexError::UnknownUnit { .. } => Some("unknown unit"),
+			DurationFlexError::UnitOutOfOrder { .. } => Some("out of order"),
+			DurationFlexError::DuplicatedUnit { .. } => Some("already specified"),
+			DurationFlexError::TrailingGarbage { .. } => Some("not a component"),
+			DurationFlexError::ComponentOverflow { .. } => Some("too large"),
+			DurationFlexError::VariableLengthUnit { .. } => Some("variable length"),
+			_ => None,
+		}
+	}
+
+	/// Hint on how to fix the input, in terms of the grammar of the parser that rejected it.
+	pub fn help(&self) -> Option<&'static str> {
+		match self {
+			DurationFlexError::EmptyInput { grammar } => {
+				match grammar {
+					Grammar::Native | Grammar::Go | Grammar::Prometheus => {
+						Some("specify at least one component, like `1h30m`")
+					},
+					Grammar::Iso8601 => Some("specify at least one component, like `PT1H30M`"),
+					Grammar::Clock => Some("the format is `[-][d.]hh:mm[:ss[.fffffffff]]`, like `01:30:00`"),
+					Grammar::Postgres => {
+						Some("specify at least one component, like `1 day 02:30:00` or `1 hour 30 mins`")
+					},
+					Grammar::Systemd => Some("specify at least one component, like `1h 30min`"),
+				}
+			},
+			DurationFlexError::UnknownUnit { grammar, .. } => {
+				match grammar {
+					Grammar::Native => Some("valid units are w, d, h, m, s, ms, us and ns"),
+					Grammar::Go => Some("valid units are h, m, s, ms, us (or µs) and ns"),
+					Grammar::Iso8601 => {
+						Some("date designators are Y, M, W and D, and time designators, after `T`, are H, M and S")
+					},
+					Grammar::Postgres => {
+						Some(
+							"valid units are weeks, days, hours, minutes, seconds, milliseconds and microseconds, or \
+							 their abbreviations like `mins`",
+						)
+					},
+					Grammar::Prometheus => Some("valid units are y, w, d, h, m, s and ms"),
+					Grammar::Systemd => {
+						Some(
+							"valid units are y, month, w, d, h, min, s, ms, us and ns, or their long names like \
+							 `minutes`",
+						)
+					},
+					_ => None,
+				}
+			},
+			DurationFlexError::UnitOutOfOrder { grammar, .. } => {
+				match grammar {
+					Grammar::Native => Some("units must be in the order w, d, h, m, s, ms, us and ns, like `1h30m`"),
+					Grammar::Iso8601 => {
+						Some("designators must be in the order Y, M, W, D, then `T` and H, M, S, like `P1DT1H30M`")
+					},
+					Grammar::Prometheus => Some("units must be in the order y, w, d, h, m, s and ms, like `1h30m`"),
+					_ => None,
+				}
+			},
+			DurationFlexError::DuplicatedUnit { .. } => Some("each unit can be specified only once"),
+			DurationFlexError::TrailingGarbage { grammar, .. } => {
+				match grammar {
+					Grammar::Native => {
+						Some("each component is a number followed by one of the units w, d, h, m, s, ms, us or ns")
+					},
+					Grammar::Clock => Some("the format is `[-][d.]hh:mm[:ss[.fffffffff]]`, like `01:30:00`"),
+					Grammar::Go => {
+						Some(
+							"each component is a number, optionally with a fraction, followed by one of the units h, \
+							 m, s, ms, us (or µs) or ns",
+						)
+					},
+					Grammar::Iso8601 => Some("the format is `P[nY][nM][nW][nD][T[nH][nM][nS]]`, like `P1DT1H30M`"),
+					Grammar::Postgres => {
+						Some("each component is a number followed by a unit, like `1 day`, or a time, like `02:30:00`")
+					},
+					Grammar::Prometheus => {
+						Some("each component is an integer followed by one of the units y, w, d, h, m, s or ms")
+					},
+					Grammar::Systemd => {
+						Some("each component is a number followed by an optional unit, like `1h 30min`")
+					},
+				}
+			},
+			DurationFlexError::VariableLengthUnit { .. } => {
+				Some("months and years don't have a fixed length, use weeks or days instead")
+			},
+			_ => None,
+		}
+	}
+
+	/// Renders the error against the `input` that caused it, with a caret pointing at the offending text:
+	/// ```text
+	/// error: unknown unit `x` at offset 4
+	///   |
+	///   | 1h30x
+	///   |     ^ unknown unit
+	///   |
+	///   = help: valid units are w, d, h, m, s, ms, us and ns
+	/// ```
+	pub fn report<'a>(&'a self, input: &'a str) -> ErrorReport<'a> {
+		ErrorReport { error: self, input }
+	}
+}
+
+/// Error rendered against its input, see [`DurationFlexError::report`].
+#[derive(Copy, Clone, Debug)]
+pub struct ErrorReport<'a> {
+	error: &'a DurationFlexError,
+	input: &'a str,
+}
+
+impl Display for ErrorReport<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		writeln!(f, "error: {}", self.error)?;
+
+		if let Some(span) = self.error.span().filter(|span| span.end <= self.input.len()) {
+			let padding = self.input[..span.start].chars().count();
+			let width = self.input[span.clone()].chars().count().max(1);
+
+			writeln!(f, "  |")?;
+			writeln!(f, "  | {}", self.input)?;
+			write!(f, "  | {}{}", " ".repeat(padding), "^".repeat(width))?;
+			match self.error.label() {
+				Some(label) => writeln!(f, " {}", label)?,
+				None => writeln!(f)?,
+			}
+		}
+
+		if let Some(help) = self.error.help() {
+			writeln!(f, "  |")?;
+			writeln!(f, "  = help: {}", help)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Display for DurationFlexError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			DurationFlexError::InvalidFormat => write!(f, "invalid duration format"),
+			DurationFlexError::OutOfRange => write!(f, "duration out of range"),
+			DurationFlexError::NotANumber => write!(f, "duration is not a number"),
+			DurationFlexError::Infinite => write!(f, "duration is infinite"),
+			DurationFlexError::InvalidQuantum => write!(f, "rounding quantum must be positive"),
+			DurationFlexError::EmptyInput { .. } => write!(f, "empty duration"),
+			DurationFlexError::UnknownUnit { offset, unit, .. } => {
+				write!(f, "unknown unit `{}` at offset {}", unit, offset)
+			},
+			DurationFlexError::UnitOutOfOrder { offset, unit, .. } => {
+				write!(f, "unit `{}` at offset {} is out of order", unit, offset)
+			},
+			DurationFlexError::DuplicatedUnit { offset, unit, .. } => {
+				write!(f, "unit `{}` at offset {} was already specified", unit, offset)
+			},
+			DurationFlexError::TrailingGarbage { offset, text, .. } => {
+				write!(f, "unexpected `{}` at offset {}", text, offset)
+			},
+			DurationFlexError::ComponentOverflow { offset, text } => {
+				write!(f, "component `{}` at offset {} is too large", text, offset)
+			},
+			DurationFlexError::VariableLengthUnit { offset, unit } => {
+				write!(f, "unit `{}` at offset {} doesn't have a fixed length", unit, offset)
+			},
+		}?;
+
+		match self.suggestion() {
+			Some(suggestion) => write!(f, ", did you mean `{}`?", suggestion),
+			None => Ok(()),
+		}
+	}
+}
+
+impl std::error::Error for DurationFlexError {}
+
+/// With the `miette` feature, parsing errors are diagnostics labelling the offending text, which
+/// [`miette::Report::with_source_code`] renders against the input:
+/// ```
+/// use duration_flex::DurationFlex;
+///
+/// # pub fn main() {
+/// let input = "1h30x";
+/// if let Err(error) = DurationFlex::try_from(input) {
+/// 	eprintln!("{:?}", miette::Report::new(error).with_source_code(input));
+/// }
+/// # }
+/// ```
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for DurationFlexError {
+	fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+		let code = match self {
+			DurationFlexError::InvalidFormat => "duration_flex::invalid_format",
+			DurationFlexError::OutOfRange => "duration_flex::out_of_range",
+			DurationFlexError::NotANumber => "duration_flex::not_a_number",
+			DurationFlexError::Infinite => "duration_flex::infinite",
+			DurationFlexError::InvalidQuantum => "duration_flex::invalid_quantum",
+			DurationFlexError::EmptyInput { .. } => "duration_flex::empty_input",
+			DurationFlexError::UnknownUnit { .. } => "duration_flex::unknown_unit",
+			DurationFlexError::UnitOutOfOrder { .. } => "duration_flex::unit_out_of_order",
+			DurationFlexError::DuplicatedUnit { .. } => "duration_flex::duplicated_unit",
+			DurationFlexError::TrailingGarbage { .. } => "duration_flex::trailing_garbage",
+			DurationFlexError::ComponentOverflow { .. } => "duration_flex::component_overflow",
+			DurationFlexError::VariableLengthUnit { .. } => "duration_flex::variable_length_unit",
+		};
+
+		Some(Box::new(code))
+	}
+
+	fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+		DurationFlexError::help(self).map(|help| Box::new(help) as Box<dyn Display>)
+	}
+
+	fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+		let label = miette::LabeledSpan::new_with_span(self.label().map(String::from), self.span()?);
+		Some(Box::new(std::iter::once(label)))
+	}
+}
@@ -0,0 +1,363 @@
+use std::fmt::{Display, Formatter};
+
+use crate::unit::Unit;
+use crate::{DurationFlex, DurationFlexError, Grammar, NANOS_PER_SEC};
+
+/// Largest magnitude of a Go `time.Duration`, in nano-seconds. Only negative durations can reach it.
+const LIMIT: u64 = 1 << 63;
+
+/// Formats a [`DurationFlex`] like Go's `time.Duration.String()`, see [`DurationFlex::go`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Go(pub(crate) DurationFlex);
+
+impl Display for Go {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		format(&self.0, f)
+	}
+}
+
+/// Parses the Go duration format, exactly like Go's `time.ParseDuration`, e.g. `1h30m`, `1.5h` or `-2m3.4s`.
+///
+/// Components can be in any order, repeated (they are summed) and have a fraction. The units are `h`, `m`, `s`, `ms`,
+/// `us` (or `µs`/`μs`) and `ns`. Just like in Go, the result must fit in an `i64` amount of nano-seconds.
+pub(crate) fn parse(input: &str) -> Result<DurationFlex, DurationFlexError> {
+	let garbage = |offset: usize| {
+		DurationFlexError::TrailingGarbage {
+			offset,
+			text: input[offset..].to_string(),
+			suggestion: None,
+			grammar: Grammar::Go,
+		}
+	};
+
+	let (negative, mut offset) = match input.as_bytes().first() {
+		Some(b'-') => (true, 1),
+		Some(b'+') => (false, 1),
+		_ => (false, 0),
+	};
+
+	if &input[offset..] == "0" {
+		return Ok(DurationFlex::ZERO);
+	} else if offset == input.len() {
+		return Err(DurationFlexError::EmptyInput { grammar: Grammar::Go });
+	}
+
+	let mut total = 0u64;
+	while offset < input.len() {
+		let start = offset;
+		let integer_end = offset + input[offset..].find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len() - offset);
+		let (fraction, number_end) = match input.as_bytes().get(integer_end) {
+			Some(b'.') => {
+				let start = integer_end + 1;
+				let end = start + input[start..].find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len() - start);
+				(&input[start..end], end)
+			},
+			_ => ("", integer_end),
+		};
+
+		let integer = &input[start..integer_end];
+		if integer.is_empty() && fraction.is_empty() {
+			return Err(garbage(start));
+		}
+
+		let unit_end = number_end
+			+ input[number_end..].find(|c: char| c == '.' || c.is_ascii_digit()).unwrap_or(input.len() - number_end);
+		let symbol = &input[number_end..unit_end];
+		if symbol.is_empty() {
+			return Err(garbage(start));
+		}
+
+		let unit = match symbol {
+			"h" => Unit::Hour,
+			"m" => Unit::Minute,
+			"s" => Unit::Second,
+			"ms" => Unit::Millisecond,
+			"us" | "µs" | "μs" => Unit::Microsecond,
+			"ns" => Unit::Nanosecond,
+			_ => {
+				return Err(DurationFlexError::UnknownUnit {
+					offset: number_end,
+					unit: symbol.to_string(),
+					suggestion: None,
+					grammar: Grammar::Go,
+				});
+			},
+		};
+
+		let overflow =
+			|| DurationFlexError::ComponentOverflow { offset: start, text: input[start..unit_end].to_string() };
+		let value = match integer {
+			"" => 0,
+			_ => integer.parse::<u64>().ok().filter(|value| *value <= LIMIT).ok_or_else(overflow)?,
+		};
+		if value > LIMIT / unit.nanos() {
+			return Err(overflow());
+		}
+
+		let mut component = value * unit.nanos();
+		let (numerator, scale) = leading_fraction(fraction);
+		if numerator > 0 {
+			// Same float arithmetic as Go, so fractions round the same way, e.g. `0.3333333333333333333h` is `20m`.
+			component += (numerator as f64 * (unit.nanos() as f64 / scale)) as u64;
+			if component > LIMIT {
+				return Err(overflow());
+			}
+		}
+
+		total = total.checked_add(component).filter(|total| *total <= LIMIT).ok_or(DurationFlexError::OutOfRange)?;
+		offset = unit_end;
+	}
+
+	if !negative && total == LIMIT {
+		return Err(DurationFlexError::OutOfRange);
+	}
+
+	let total = total as i128;
+	DurationFlex::from_total_nanos(if negative { -total } else { total }).ok_or(DurationFlexError::OutOfRange)
+}
+
+/// Numerator and denominator of a fraction, keeping as many digits as fit in the numerator, just like Go.
+fn leading_fraction(digits: &str) -> (u64, f64) {
+	let mut numerator = 0u64;
+	let mut scale = 1f64;
+
+	for digit in digits.bytes() {
+		let Some(value) =
+			numerator.checked_mul(10).map(|value| value + (digit - b'0') as u64).filter(|value| *value <= LIMIT)
+		else {
+			break;
+		};
+
+		numerator = value;
+		scale *= 10.0;
+	}
+
+	(numerator, scale)
+}
+
+/// Formats `value` like Go's `time.Duration.String()`: hours are the largest unit, and durations smaller than a second
+/// use a fractional amount of the largest unit that fits, e.g. `1h0m0s`, `1m30.5s`, `1.5µs` or `0s`.
+///
+/// Unlike Go, durations beyond 292 years are formatted as well, with a larger amount of hours.
+pub(crate) fn format(value: &DurationFlex, f: &mut Formatter<'_>) -> std::fmt::Result {
+	let total = value.total_nanos();
+	if total == 0 {
+		return f.write_str("0s");
+	} else if total < 0 {
+		f.write_str("-")?;
+	}
+
+	let total = total.unsigned_abs();
+	if total < Unit::Microsecond.nanos() as u128 {
+		write!(f, "{}ns", total)
+	} else if total < Unit::Millisecond.nanos() as u128 {
+		write_fraction(total, 3, f)?;
+		f.write_str("µs")
+	} else if total < NANOS_PER_SEC as u128 {
+		write_fraction(total, 6, f)?;
+		f.write_str("ms")
+	} else {
+		let hours = total / Unit::Hour.nanos() as u128;
+		let minutes = total % Unit::Hour.nanos() as u128 / Unit::Minute.nanos() as u128;
+
+		if hours > 0 {
+			write!(f, "{}h{}m", hours, minutes)?;
+		} else if minutes > 0 {
+			write!(f, "{}m", minutes)?;
+		}
+
+		write_fraction(total % Unit::Minute.nanos() as u128, 9, f)?;
+		f.write_str("s")
+	}
+}
+
+/// Writes `value / 10^precision`, without trailing zeros in the fraction (nor the `.` if it is all zeros).
+fn write_fraction(value: u128, precision: u32, f: &mut Formatter<'_>) -> std::fmt::Result {
+	let scale = 10u128.pow(precision);
+	let fraction = value % scale;
+
+	write!(f, "{}", value / scale)?;
+	if fraction > 0 {
+		let fraction = format!("{:0width$}", fraction, width = precision as usize);
+		write!(f, ".{}", fraction.trim_end_matches('0'))?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{DurationFlex, DurationFlexError, Grammar};
+
+	const NANOSECOND: i128 = 1;
+	const MICROSECOND: i128 = 1_000 * NANOSECOND;
+	const MILLISECOND: i128 = 1_000 * MICROSECOND;
+	const SECOND: i128 = 1_000 * MILLISECOND;
+	const MINUTE: i128 = 60 * SECOND;
+	const HOUR: i128 = 60 * MINUTE;
+
+	fn nanos(value: i128) -> DurationFlex {
+		DurationFlex::from_total_nanos(value).unwrap()
+	}
+
+	/// `parseDurationTests`, from Go's `time_test.go`.
+	#[test]
+	fn parse() {
+		let cases = [
+			// simple
+			("0", 0),
+			("5s", 5 * SECOND),
+			("30s", 30 * SECOND),
+			("1478s", 1478 * SECOND),
+			// sign
+			("-5s", -5 * SECOND),
+			("+5s", 5 * SECOND),
+			("-0", 0),
+			("+0", 0),
+			// decimal
+			("5.0s", 5 * SECOND),
+			("5.6s", 5 * SECOND + 600 * MILLISECOND),
+			("5.s", 5 * SECOND),
+			(".5s", 500 * MILLISECOND),
+			("1.0s", SECOND),
+			("1.00s", SECOND),
+			("1.004s", SECOND + 4 * MILLISECOND),
+			("1.0040s", SECOND + 4 * MILLISECOND),
+			("100.00100s", 100 * SECOND + MILLISECOND),
+			// different units
+			("10ns", 10 * NANOSECOND),
+			("11us", 11 * MICROSECOND),
+			("12µs", 12 * MICROSECOND),
+			("12μs", 12 * MICROSECOND),
+			("13ms", 13 * MILLISECOND),
+			("14s", 14 * SECOND),
+			("15m", 15 * MINUTE),
+			("16h", 16 * HOUR),
+			// composite durations
+			("3h30m", 3 * HOUR + 30 * MINUTE),
+			("10.5s4m", 4 * MINUTE + 10 * SECOND + 500 * MILLISECOND),
+			("-2m3.4s", -(2 * MINUTE + 3 * SECOND + 400 * MILLISECOND)),
+			("1h2m3s4ms5us6ns", HOUR + 2 * MINUTE + 3 * SECOND + 4 * MILLISECOND + 5 * MICROSECOND + 6 * NANOSECOND),
+			("39h9m14.425s", 39 * HOUR + 9 * MINUTE + 14 * SECOND + 425 * MILLISECOND),
+			// large value
+			("52763797000ns", 52763797000 * NANOSECOND),
+			// more than 9 digits after decimal point
+			("0.3333333333333333333h", 20 * MINUTE),
+			// 1<<53+1 cannot be stored precisely in a float64
+			("9007199254740993ns", ((1 << 53) + 1) * NANOSECOND),
+			// largest duration that can be represented by int64 in nanoseconds
+			("9223372036854775807ns", i64::MAX as i128),
+			("9223372036854775.807us", i64::MAX as i128),
+			("9223372036s854ms775us807ns", i64::MAX as i128),
+			("-9223372036854775808ns", i64::MIN as i128),
+			("-9223372036854775.808us", i64::MIN as i128),
+			("-9223372036s854ms775us808ns", i64::MIN as i128),
+			// largest negative round trip value
+			("-2562047h47m16.854775808s", i64::MIN as i128),
+			// huge string
+			("0.100000000000000000000h", 6 * MINUTE),
+			// first overflow check in leadingFraction
+			("0.830103483285477580700h", 49 * MINUTE + 48 * SECOND + 372539827 * NANOSECOND),
+		];
+
+		for (input, expected) in cases {
+			assert_eq!(DurationFlex::parse_go(input), Ok(nanos(expected)), "{}", input);
+		}
+	}
+
+	/// `parseDurationErrorTests`, from Go's `time_test.go`, except the invalid UTF-8 ones.
+	#[test]
+	fn parse_errors() {
+		let cases = [
+			// invalid
+			"",
+			"3",
+			"-",
+			"s",
+			".",
+			"-.",
+			".s",
+			"+.s",
+			"1d",
+			"\u{FFFD}",
+			"\u{FFFD} hello \u{FFFD} world",
+			// overflow
+			"9223372036854775810ns",
+			"9223372036854775808ns",
+			"-9223372036854775809ns",
+			"9223372036854776us",
+			"3000000h",
+			"9223372036854775.808us",
+			"9223372036854ms775us808ns",
+		];
+
+		for input in cases {
+			assert!(DurationFlex::parse_go(input).is_err(), "{}", input);
+		}
+
+		assert_eq!(DurationFlex::parse_go(""), Err(DurationFlexError::EmptyInput { grammar: Grammar::Go }));
+		assert_eq!(
+			DurationFlex::parse_go("1h30"),
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 2,
+				text: "30".to_string(),
+				suggestion: None,
+				grammar: Grammar::Go
+			})
+		);
+		assert_eq!(
+			DurationFlex::parse_go("1d"),
+			Err(DurationFlexError::UnknownUnit {
+				offset: 1,
+				unit: "d".to_string(),
+				suggestion: None,
+				grammar: Grammar::Go
+			})
+		);
+		assert_eq!(DurationFlex::parse_go("9223372036854775808ns"), Err(DurationFlexError::OutOfRange));
+		assert_eq!(
+			DurationFlex::parse_go("3000000h"),
+			Err(DurationFlexError::ComponentOverflow { offset: 0, text: "3000000h".to_string() })
+		);
+	}
+
+	#[test]
+	fn help() {
+		let help = |input: &str| DurationFlex::parse_go(input).unwrap_err().help();
+
+		assert_eq!(help("1d"), Some("valid units are h, m, s, ms, us (or µs) and ns"));
+		assert_eq!(
+			help("1h30"),
+			Some(
+				"each component is a number, optionally with a fraction, followed by one of the units h, m, s, ms, us \
+				 (or µs) or ns"
+			)
+		);
+	}
+
+	/// `durationTests`, from Go's `time_test.go`.
+	#[test]
+	fn format() {
+		let cases = [
+			("0s", 0),
+			("1ns", NANOSECOND),
+			("1.1µs", 1100 * NANOSECOND),
+			("2.2ms", 2200 * MICROSECOND),
+			("3.3s", 3300 * MILLISECOND),
+			("4m5s", 4 * MINUTE + 5 * SECOND),
+			("4m5.001s", 4 * MINUTE + 5001 * MILLISECOND),
+			("5h6m7.001s", 5 * HOUR + 6 * MINUTE + 7001 * MILLISECOND),
+			("8m0.000000001s", 8 * MINUTE + NANOSECOND),
+			("2562047h47m16.854775807s", i64::MAX as i128),
+			("-2562047h47m16.854775808s", i64::MIN as i128),
+		];
+
+		for (expected, value) in cases {
+			assert_eq!(nanos(value).go().to_string(), expected);
+			assert_eq!(DurationFlex::parse_go(expected), Ok(nanos(value)));
+		}
+
+		assert_eq!(nanos(HOUR).go().to_string(), "1h0m0s");
+		assert_eq!(nanos(-90 * SECOND).go().to_string(), "-1m30s");
+	}
+}
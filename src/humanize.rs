@@ -0,0 +1,194 @@
+use std::fmt::{Display, Formatter};
+
+use crate::locale::Locale;
+use crate::unit::Unit;
+use crate::DurationFlex;
+
+/// How unit names are abbreviated by [`Humanize`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Abbreviation {
+	/// Full names, from [`Locale::names`], e.g. `1 hour and 30 minutes`.
+	#[default]
+	Full,
+	/// Common abbreviations, from [`Locale::short_names`], e.g. `1 hr and 30 mins`.
+	Short,
+	/// Unit symbols, as used by [`std::fmt::Display`], e.g. `1h and 30m`.
+	Symbol,
+}
+
+impl Abbreviation {
+	/// Name of `unit` in `locale`, for `amount`.
+	fn name(self, locale: &Locale, unit: Unit, amount: u128) -> &'static str {
+		let forms = match self {
+			Abbreviation::Full => locale.names[unit as usize],
+			Abbreviation::Short => locale.short_names[unit as usize],
+			Abbreviation::Symbol => return unit.symbol(),
+		};
+
+		forms.get(locale.plural.form(amount)).or(forms.last()).copied().unwrap_or_default()
+	}
+}
+
+/// Formats a [`DurationFlex`] for humans, e.g. `1 hour and 30 minutes`, see [`DurationFlex::humanize`].
+#[derive(Copy, Clone, Debug)]
+pub struct Humanize<'a> {
+	value: DurationFlex,
+	separator: &'a str,
+	conjunction: Option<&'a str>,
+	max_components: usize,
+	abbreviation: Abbreviation,
+	locale: &'a Locale,
+}
+
+impl<'a> Humanize<'a> {
+	pub(crate) fn new(value: DurationFlex) -> Self {
+		let locale = &Locale::EN;
+
+		Humanize {
+			value,
+			separator: locale.separator,
+			conjunction: locale.conjunction,
+			max_components: usize::MAX,
+			abbreviation: Abbreviation::Full,
+			locale,
+		}
+	}
+
+	/// Text between components, from the locale, so `, ` by default.
+	pub fn separator(mut self, value: &'a str) -> Self {
+		self.separator = value;
+		self
+	}
+
+	/// Word before the last component, instead of the separator, from the locale, so `and` by default, which gives
+	/// `1 hour, 30 minutes and 5 seconds`. `None` uses the separator, e.g. `1 hour, 30 minutes, 5 seconds`.
+	pub fn conjunction(mut self, value: Option<&'a str>) -> Self {
+		self.conjunction = value;
+		self
+	}
+
+	/// Maximum amount of components (at least 1), the smaller ones are truncated, e.g. `1 hour and 30 minutes` for
+	/// `1h30m5s` with 2.
+	pub fn max_components(mut self, value: usize) -> Self {
+		self.max_components = value.max(1);
+		self
+	}
+
+	/// How unit names are abbreviated, see [`Abbreviation`].
+	pub fn abbreviation(mut self, value: Abbreviation) -> Self {
+		self.abbreviation = value;
+		self
+	}
+
+	/// Language of the unit names, English by default. Also sets the separator and conjunction of the locale, e.g.
+	/// `1 hora, 30 minutos e 5 segundos` with `Locale::PT`, so call [`Humanize::separator`] or
+	/// [`Humanize::conjunction`] after it to override them.
+	pub fn locale(mut self, value: &'a Locale) -> Self {
+		self.locale = value;
+		self.separator = value.separator;
+		self.conjunction = value.conjunction;
+		self
+	}
+}
+
+impl Display for Humanize<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let total = self.value.total_nanos();
+		if total < 0 {
+			f.write_str("-")?;
+		}
+
+		let mut components: Vec<_> = self
+			.value
+			.components()
+			.iter()
+			.filter(|(_, amount)| *amount != 0)
+			.map(|(unit, amount)| (amount.unsigned_abs() as u128, unit))
+			.collect();
+
+		if components.is_empty() {
+			components.push((0, Unit::Second));
+		}
+		components.truncate(self.max_components);
+
+		for (index, (amount, unit)) in components.iter().enumerate() {
+			match self.conjunction {
+				_ if index == 0 => {},
+				Some(conjunction) if index == components.len() - 1 => write!(f, " {} ", conjunction)?,
+				_ => f.write_str(self.separator)?,
+			}
+
+			let name = self.abbreviation.name(self.locale, *unit, *amount);
+			match self.abbreviation {
+				Abbreviation::Symbol => write!(f, "{}{}", amount, name)?,
+				_ => write!(f, "{}{}{}", amount, self.locale.spacing, name)?,
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{flex, Abbreviation, DurationFlex};
+
+	#[test]
+	fn humanize() {
+		assert_eq!(flex("1h30m").humanize().to_string(), "1 hour and 30 minutes");
+		assert_eq!(flex("2h1m").humanize().to_string(), "2 hours and 1 minute");
+		assert_eq!(flex("0s").humanize().to_string(), "0 seconds");
+		assert_eq!(flex("1s").humanize().to_string(), "1 second");
+		assert_eq!(flex("-1w1ns").humanize().to_string(), "-1 week and 1 nanosecond");
+		assert_eq!(
+			flex("1d2ms3us4ns").humanize().to_string(),
+			"1 day, 2 milliseconds, 3 microseconds and 4 nanoseconds"
+		);
+	}
+
+	#[test]
+	fn options() {
+		assert_eq!(flex("1h30m5s").humanize().to_string(), "1 hour, 30 minutes and 5 seconds");
+		assert_eq!(flex("1h30m5s").humanize().conjunction(None).to_string(), "1 hour, 30 minutes, 5 seconds");
+		assert_eq!(flex("1h30m5s").humanize().conjunction(Some("&")).to_string(), "1 hour, 30 minutes & 5 seconds");
+		assert_eq!(flex("1h").humanize().to_string(), "1 hour");
+		assert_eq!(flex("1h30m5s").humanize().separator(" ").to_string(), "1 hour 30 minutes and 5 seconds");
+		assert_eq!(
+			flex("1h30m5s").humanize().separator(" ").conjunction(None).to_string(),
+			"1 hour 30 minutes 5 seconds"
+		);
+		assert_eq!(flex("1h30m5s").humanize().max_components(2).to_string(), "1 hour and 30 minutes");
+		assert_eq!(flex("1h30m5s").humanize().max_components(0).to_string(), "1 hour");
+		assert_eq!(
+			flex("2w1h1m2s500ms").humanize().abbreviation(Abbreviation::Short).to_string(),
+			"2 wks, 1 hr, 1 min, 2 secs and 500 ms"
+		);
+		assert_eq!(
+			flex("1h30m").humanize().abbreviation(Abbreviation::Symbol).conjunction(None).to_string(),
+			"1h, 30m"
+		);
+		assert_eq!(flex("0s").humanize().abbreviation(Abbreviation::Symbol).to_string(), "0s");
+
+		let separator = String::from(" | ");
+		assert_eq!(flex("1h30m5s").humanize().separator(&separator).to_string(), "1 hour | 30 minutes and 5 seconds");
+	}
+
+	#[test]
+	fn alternate() {
+		assert_eq!(format!("{:#}", flex("1h30m")), "1 hour and 30 minutes");
+		assert_eq!(format!("{}", flex("1h30m")), "1h30m");
+	}
+
+	#[test]
+	fn round_trip() {
+		let parser = DurationFlex::parser().allow_whitespace(true).long_units(true);
+
+		for value in ["1w2d3h4m5s6ms7us8ns", "1h30m", "-2h1m"] {
+			for abbreviation in [Abbreviation::Full, Abbreviation::Short, Abbreviation::Symbol] {
+				let text =
+					flex(value).humanize().abbreviation(abbreviation).separator(" ").conjunction(None).to_string();
+				assert_eq!(parser.parse(&text), Ok(flex(value)), "{}", text);
+			}
+		}
+	}
+}
@@ -0,0 +1,339 @@
+use std::fmt::{Display, Formatter};
+
+use crate::unit::Unit;
+use crate::{DurationFlex, DurationFlexError, Grammar, NANOS_PER_SEC};
+
+/// Formats a [`DurationFlex`] as an ISO 8601 duration, see [`DurationFlex::iso8601`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Iso8601(pub(crate) DurationFlex);
+
+impl Display for Iso8601 {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		format(&self.0, f)
+	}
+}
+
+#[cfg(feature = "utoipa")]
+impl Iso8601 {
+	/// OpenAPI schema of a [`DurationFlex`] in the ISO 8601 format, a String with the `duration` format. To be used
+	/// with fields (de)serialized with [`crate::serde::as_iso8601`]:
+	/// ```
+	/// use duration_flex::{DurationFlex, Iso8601};
+	///
+	/// #[derive(utoipa::ToSchema)]
+	/// struct Config {
+	/// 	#[schema(schema_with = Iso8601::schema)]
+	/// 	timeout: DurationFlex,
+	/// }
+	/// ```
+	pub fn schema() -> utoipa::openapi::schema::Object {
+		use utoipa::openapi::schema::{KnownFormat, ObjectBuilder, SchemaFormat, Type};
+
+		ObjectBuilder::new()
+			.schema_type(Type::String)
+			.format(Some(SchemaFormat::KnownFormat(KnownFormat::Duration)))
+			.examples(["PT1H30M"])
+			.build()
+	}
+}
+
+/// Parses the ISO 8601 duration format, like `P1W`, `PT1H30M` or `-P2DT3H4M5.5S`.
+///
+/// Only fixed-length designators are supported: weeks (`W`) and days (`D`), followed by a `T` and hours (`H`),
+/// minutes (`M`) and seconds (`S`). Days are always 24 hours long. The smallest component may have a fraction, using
+/// either `.` or `,` as decimal separator.
+pub(crate) fn parse(input: &str) -> Result<DurationFlex, DurationFlexError> {
+	let garbage = |offset: usize| {
+		DurationFlexError::TrailingGarbage {
+			offset,
+			text: input[offset..].to_string(),
+			suggestion: None,
+			grammar: Grammar::Iso8601,
+		}
+	};
+
+	let (negative, mut offset) = match input.as_bytes().first() {
+		Some(b'-') => (true, 1),
+		Some(b'+') => (false, 1),
+		_ => (false, 0),
+	};
+
+	if input.as_bytes().get(offset) != Some(&b'P') {
+		return Err(garbage(offset));
+	}
+	offset += 1;
+
+	if offset == input.len() {
+		return Err(DurationFlexError::EmptyInput { grammar: Grammar::Iso8601 });
+	}
+
+	let mut total = 0i128;
+	let mut previous: Option<Unit> = None;
+	let mut in_time = false;
+	let mut fractional = false;
+
+	while offset < input.len() {
+		if input.as_bytes()[offset] == b'T' {
+			if in_time || offset + 1 == input.len() {
+				return Err(garbage(offset));
+			}
+
+			in_time = true;
+			offset += 1;
+			continue;
+		}
+
+		if fractional {
+			return Err(garbage(offset));
+		}
+
+		let component = scan_component(input, offset, in_time, false)?;
+		let designator = &input[component.designator..component.end];
+
+		match previous {
+			Some(previous) if previous == component.unit => {
+				return Err(DurationFlexError::DuplicatedUnit {
+					offset: component.designator,
+					unit: designator.to_string(),
+					suggestion: None,
+				});
+			},
+			Some(previous) if previous.nanos() < component.unit.nanos() => {
+				return Err(DurationFlexError::UnitOutOfOrder {
+					offset: component.designator,
+					unit: designator.to_string(),
+					suggestion: None,
+					grammar: Grammar::Iso8601,
+				});
+			},
+			_ => {},
+		}
+
+		total = total.checked_add(component.nanos).ok_or(DurationFlexError::OutOfRange)?;
+		previous = Some(component.unit);
+		fractional = component.fractional;
+		offset = component.end;
+	}
+
+	DurationFlex::from_total_nanos(if negative { -total } else { total }).ok_or(DurationFlexError::OutOfRange)
+}
+
+/// Component of the designator format, like `1.5H`, see [`scan_component`].
+pub(crate) struct Component {
+	/// Unit of the designator.
+	pub(crate) unit: Unit,
+	/// Amount of nano-seconds, negative if the component has its own `-` sign.
+	pub(crate) nanos: i128,
+	/// Whether the amount has a fraction.
+	pub(crate) fractional: bool,
+	/// Byte offset of the designator.
+	pub(crate) designator: usize,
+	/// Byte offset where the component ends.
+	pub(crate) end: usize,
+}
+
+/// Scans the component at `offset`, like `1.5H`, either in the date part (weeks and days) or, with `in_time`, in the
+/// time part (hours, minutes and seconds). With `signed`, the amount can have its own sign, like `-2H` in PostgreSQL.
+pub(crate) fn scan_component(
+	input: &str,
+	offset: usize,
+	in_time: bool,
+	signed: bool,
+) -> Result<Component, DurationFlexError> {
+	let garbage = |offset: usize| {
+		DurationFlexError::TrailingGarbage {
+			offset,
+			text: input[offset..].to_string(),
+			suggestion: None,
+			grammar: Grammar::Iso8601,
+		}
+	};
+	let digits_end =
+		|start: usize| start + input[start..].find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len() - start);
+
+	let (negative, start) = match input.as_bytes().get(offset) {
+		Some(b'-') if signed => (true, offset + 1),
+		Some(b'+') if signed => (false, offset + 1),
+		_ => (false, offset),
+	};
+
+	let integer_end = digits_end(start);
+	let (fraction, number_end) = match input.as_bytes().get(integer_end) {
+		Some(b'.' | b',') => {
+			let end = digits_end(integer_end + 1);
+			if end == integer_end + 1 {
+				return Err(garbage(integer_end));
+			}
+
+			(&input[integer_end + 1..end], end)
+		},
+		_ => ("", integer_end),
+	};
+
+	let integer = &input[start..integer_end];
+	let Some(designator) = input[number_end..].chars().next().filter(|_| !integer.is_empty()) else {
+		return Err(garbage(offset));
+	};
+
+	let unit = match (in_time, designator) {
+		(false, 'W') => Unit::Week,
+		(false, 'D') => Unit::Day,
+		(true, 'H') => Unit::Hour,
+		(true, 'M') => Unit::Minute,
+		(true, 'S') => Unit::Second,
+		(false, 'Y' | 'M') => {
+			return Err(DurationFlexError::VariableLengthUnit { offset: number_end, unit: designator.to_string() });
+		},
+		_ => {
+			return Err(DurationFlexError::UnknownUnit {
+				offset: number_end,
+				unit: designator.to_string(),
+				suggestion: None,
+				grammar: Grammar::Iso8601,
+			});
+		},
+	};
+
+	let end = number_end + designator.len_utf8();
+	let nanos = amount_nanos(integer, fraction, unit)
+		.ok_or_else(|| DurationFlexError::ComponentOverflow { offset, text: input[offset..end].to_string() })?;
+
+	Ok(Component {
+		unit,
+		nanos: if negative { -nanos } else { nanos },
+		fractional: !fraction.is_empty(),
+		designator: number_end,
+		end,
+	})
+}
+
+/// Amount of nano-seconds in `integer` and `fraction` (the digits after the decimal separator) `unit`s, or `None` if
+/// it doesn't fit in a duration.
+pub(crate) fn amount_nanos(integer: &str, fraction: &str, unit: Unit) -> Option<i128> {
+	let integer = match integer {
+		"" => 0,
+		integer => integer.parse::<u64>().ok()?,
+	};
+
+	let mut value = integer as i128 * unit.nanos() as i128;
+	// Digits beyond the 18th can't change the result by a full nano-second, even for weeks.
+	let fraction = &fraction[..fraction.len().min(18)];
+	if !fraction.is_empty() {
+		value += fraction.parse::<i128>().ok()? * unit.nanos() as i128 / 10i128.pow(fraction.len() as u32);
+	}
+
+	DurationFlex::from_total_nanos(value).map(|_| value)
+}
+
+/// Formats `value` as an ISO 8601 duration, using days as the largest unit, e.g. `P1DT2H3M4.5S`. Zero is `PT0S`.
+pub(crate) fn format(value: &DurationFlex, f: &mut Formatter<'_>) -> std::fmt::Result {
+	let total = value.total_nanos();
+	if total == 0 {
+		return f.write_str("PT0S");
+	} else if total < 0 {
+		f.write_str("-")?;
+	}
+
+	let total = total.unsigned_abs();
+	let days = total / Unit::Day.nanos() as u128;
+	let hours = total % Unit::Day.nanos() as u128 / Unit::Hour.nanos() as u128;
+	let minutes = total % Unit::Hour.nanos() as u128 / Unit::Minute.nanos() as u128;
+	let secs = total % Unit::Minute.nanos() as u128 / NANOS_PER_SEC as u128;
+	let nanos = total % NANOS_PER_SEC as u128;
+
+	f.write_str("P")?;
+	if days > 0 {
+		write!(f, "{}D", days)?;
+	}
+
+	if hours == 0 && minutes == 0 && secs == 0 && nanos == 0 {
+		return Ok(());
+	}
+
+	f.write_str("T")?;
+	if hours > 0 {
+		write!(f, "{}H", hours)?;
+	}
+	if minutes > 0 {
+		write!(f, "{}M", minutes)?;
+	}
+	if nanos > 0 {
+		let fraction = format!("{:09}", nanos);
+		write!(f, "{}.{}S", secs, fraction.trim_end_matches('0'))?;
+	} else if secs > 0 {
+		write!(f, "{}S", secs)?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{flex, DurationFlex, DurationFlexError, Grammar};
+
+	#[test]
+	fn parse() {
+		assert_eq!(DurationFlex::parse_iso8601("P1W"), Ok(flex("1w")));
+		assert_eq!(DurationFlex::parse_iso8601("P2DT3H4M5.5S"), Ok(flex("2d3h4m5s500ms")));
+		assert_eq!(DurationFlex::parse_iso8601("PT1H30M"), Ok(flex("1h30m")));
+		assert_eq!(DurationFlex::parse_iso8601("PT0,25S"), Ok(flex("250ms")));
+		assert_eq!(DurationFlex::parse_iso8601("PT1.5H"), Ok(flex("1h30m")));
+		assert_eq!(DurationFlex::parse_iso8601("-P1DT0.000000001S"), Ok(flex("-1d1ns")));
+		assert_eq!(DurationFlex::parse_iso8601("+PT0S"), Ok(flex("0s")));
+
+		assert_eq!(DurationFlex::parse_iso8601("P"), Err(DurationFlexError::EmptyInput { grammar: Grammar::Iso8601 }));
+		assert!(DurationFlex::parse_iso8601("PT").is_err());
+		assert!(DurationFlex::parse_iso8601("P1DT").is_err());
+		assert!(DurationFlex::parse_iso8601("1D").is_err());
+		assert!(DurationFlex::parse_iso8601("P1H").is_err());
+		assert!(DurationFlex::parse_iso8601("PT1D").is_err());
+		assert!(DurationFlex::parse_iso8601("PT1.5H30M").is_err());
+		assert!(DurationFlex::parse_iso8601("PT30M1H").is_err());
+		assert!(DurationFlex::parse_iso8601("PT1H1H").is_err());
+		assert!(DurationFlex::parse_iso8601("PTH").is_err());
+		assert!(DurationFlex::parse_iso8601("PT1.S").is_err());
+
+		assert_eq!(
+			DurationFlex::parse_iso8601("P1Y"),
+			Err(DurationFlexError::VariableLengthUnit { offset: 2, unit: "Y".to_string() })
+		);
+		assert_eq!(
+			DurationFlex::parse_iso8601("P1M2D"),
+			Err(DurationFlexError::VariableLengthUnit { offset: 2, unit: "M".to_string() })
+		);
+	}
+
+	#[test]
+	fn help() {
+		let help = |input: &str| DurationFlex::parse_iso8601(input).unwrap_err().help();
+
+		assert_eq!(
+			help("PT1X"),
+			Some("date designators are Y, M, W and D, and time designators, after `T`, are H, M and S")
+		);
+		assert_eq!(
+			help("PT1M1H"),
+			Some("designators must be in the order Y, M, W, D, then `T` and H, M, S, like `P1DT1H30M`")
+		);
+		assert_eq!(help("1H"), Some("the format is `P[nY][nM][nW][nD][T[nH][nM][nS]]`, like `P1DT1H30M`"));
+	}
+
+	#[test]
+	fn format() {
+		assert_eq!(flex("0s").iso8601().to_string(), "PT0S");
+		assert_eq!(flex("1w").iso8601().to_string(), "P7D");
+		assert_eq!(flex("2d3h4m5s500ms").iso8601().to_string(), "P2DT3H4M5.5S");
+		assert_eq!(flex("1h30m").iso8601().to_string(), "PT1H30M");
+		assert_eq!(flex("-1d1ns").iso8601().to_string(), "-P1DT0.000000001S");
+		assert_eq!(flex("250ms").iso8601().to_string(), "PT0.25S");
+	}
+
+	#[cfg(feature = "utoipa")]
+	#[test]
+	fn schema() {
+		use utoipa::openapi::schema::{KnownFormat, SchemaFormat};
+
+		let schema = super::Iso8601::schema();
+		assert!(matches!(schema.format, Some(SchemaFormat::KnownFormat(KnownFormat::Duration))));
+	}
+}
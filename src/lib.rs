@@ -5,6 +5,9 @@
 //! - Basic interoperability with [`chrono::DateTime`], allowing it to be added/subbed from it.
 //! - Can be built from [`chrono::Duration`].
 //! - Can be built from [`std::time::Duration`].
+//! - Arithmetic between durations and with integers, like `timeout * retries`, with `checked_*`, `saturating_*` and
+//!   `overflowing_*` variants.
+//! - Rounding to a multiple of another duration or to a unit, like the nearest 5 minutes, see [`RoundingMode`].
 //!
 //! **Example:**
 //! - 1 hour and 23 minutes: `1h23m`
@@ -16,6 +19,11 @@
 //! - Hours: `15h` (15 hours).
 //! - Minutes: `5m` (5 minutes).
 //! - Seconds: `30s` (30 seconds).
+//! - Milliseconds: `250ms` (250 milliseconds).
+//! - Microseconds: `100us` or `100µs` (100 microseconds).
+//! - Nanoseconds: `10ns` (10 nanoseconds).
+//!
+//! Durations can be negative, by prefixing them with `-`, like `-1h30m`. A leading `+` is also accepted.
 //!
 //! ## Usage
 //!
@@ -29,10 +37,100 @@
 //! # }
 //! ```
 //!
+//! Parsing errors point at the offending component, and can be rendered for humans with
+//! [`DurationFlexError::report`]:
+//! ```
+//! use duration_flex::DurationFlex;
+//!
+//! # pub fn main() {
+//! let input = "1h30x";
+//! if let Err(error) = DurationFlex::try_from(input) {
+//! 	eprintln!("{}", error.report(input));
+//! }
+//! # }
+//! ```
+//!
+//! ### Lenient Parsing
+//!
+//! [`DurationFlex::parser`] builds a parser that can be relaxed to accept whitespace, long unit names, any case or
+//! order, repeated units and bare numbers:
+//! ```
+//! use duration_flex::{DurationFlex, Unit};
+//!
+//! # pub fn main() {
+//! let parser = DurationFlex::parser()
+//! 	.allow_whitespace(true)
+//! 	.case_insensitive(true)
+//! 	.any_order(true)
+//! 	.sum_repeated(true)
+//! 	.long_units(true)
+//! 	.default_unit(Unit::Second);
+//!
+//! assert_eq!(parser.parse("30 Minutes 1 hour").unwrap().to_string(), "1h30m");
+//! assert_eq!(parser.parse("45").unwrap().to_string(), "45s");
+//! # }
+//! ```
+//!
+//! ### Humanize
+//!
+//! [`DurationFlex::humanize`] formats for humans, like `1 hour and 30 minutes`, which is also what `{:#}` produces.
+//!
+//! It can be translated with a [`Locale`], which [`ParseOptions::locale`] also understands, so users can type
+//! durations in their own language:
+//! ```
+//! # #[cfg(feature = "i18n")]
+//! # {
+//! use duration_flex::{DurationFlex, Locale};
+//!
+//! let value = DurationFlex::try_from("1h30m").unwrap();
+//! assert_eq!(value.humanize().locale(&Locale::PT).to_string(), "1 hora e 30 minutos");
+//!
+//! let parser = DurationFlex::parser().allow_whitespace(true).locale(&Locale::PT);
+//! assert_eq!(parser.parse("1 hora e 30 minutos"), Ok(value));
+//! # }
+//! ```
+//!
+//! ### Clock
+//!
+//! [`DurationFlex::parse_clock`] and [`DurationFlex::clock`] speak the `[d.]hh:mm[:ss[.fraction]]` format, like
+//! `01:30:00` or `2.04:00:00`.
+//!
+//! ### ISO 8601
+//!
+//! [`DurationFlex::parse_iso8601`] and [`DurationFlex::iso8601`] speak the ISO 8601 designator format, like
+//! `PT1H30M`, as commonly used by OpenAPI contracts.
+//!
+//! ### Go
+//!
+//! [`DurationFlex::parse_go`] and [`DurationFlex::go`] match Go's `time.ParseDuration` and `time.Duration.String()`,
+//! so values can be shared verbatim with Go services, like `1h0m0s` or `1.5h`.
+//!
+//! ### PostgreSQL
+//!
+//! [`DurationFlex::parse_postgres`] and [`DurationFlex::postgres`] speak the text format of PostgreSQL `interval`s, in
+//! any of its [`IntervalStyle`]s, like `1 day 02:03:04.5` or `@ 1 hour 30 mins`.
+//!
+//! ### Prometheus
+//!
+//! [`DurationFlex::parse_prometheus`] and [`DurationFlex::prometheus`] match Prometheus' `model.ParseDuration` and
+//! `model.Duration.String()`, as used by PromQL and alerting rules, like `5m` or `1y`.
+//!
+//! ### systemd
+//!
+//! [`DurationFlex::parse_systemd`] and [`DurationFlex::systemd`] implement the systemd.time(7) timespans, as used in
+//! unit files, like `2min 30s` or `5 hours`.
+//!
 //! ## Features
 //! - `clap`: enable clap support, so it can be used as application arguments.
-//! - `serde`: enable serde support.
-//! - `utoipa`: enable support for the [`utoipa`] crate, allowing it to be used with the `ToSchema` derivation.
+//! - `clock`: make [`DurationFlex::from_str`] (and so clap) also accept the clock format, like `01:30:00`.
+//! - `i18n`: bundle the Portuguese, Spanish, German and Japanese [`Locale`]s.
+//! - `miette`: implement [`miette::Diagnostic`] for [`DurationFlexError`], labelling the offending component of the
+//!   input.
+//! - `serde`: enable serde support. Human-readable formats (like JSON) use the `1h30m` String, while binary formats
+//!   (like bincode) use a compact `(secs, nanos)` tuple. See [`serde`](mod@crate::serde) for alternative
+//!   representations.
+//! - `utoipa`: enable support for the [`utoipa`] crate, allowing it to be used with the `ToSchema` derivation. Use
+//!   [`Iso8601::schema`] for fields in the ISO 8601 format.
 //! - `validator`: enable support for the [`validator`] crate, allowing it to be used with the `range` validator.
 //!
 //! ### Validator Example:
@@ -87,38 +185,75 @@
 //! ```
 
 use std::fmt::{Display, Formatter};
-use std::ops::{Add, Sub};
+use std::ops::Add;
 use std::str::FromStr;
 use std::time;
 
+#[cfg(feature = "serde")]
+use ::serde::de::{Error, MapAccess, SeqAccess, Unexpected, Visitor};
+#[cfg(feature = "serde")]
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
 use chrono::{DateTime, Duration, TimeZone};
 #[cfg(feature = "clap")]
 use clap::builder::OsStr;
-use once_cell::sync::Lazy;
-use regex::{Match, Regex};
-#[cfg(feature = "serde")]
-use serde::de::{Error, Unexpected, Visitor};
+
+pub use crate::builder::Builder;
+pub use crate::clock::Clock;
+pub use crate::components::Components;
+pub use crate::error::{DurationFlexError, ErrorReport, Grammar};
+pub use crate::go::Go;
+pub use crate::humanize::{Abbreviation, Humanize};
+pub use crate::iso8601::Iso8601;
+pub use crate::locale::{Locale, PluralRule};
+pub use crate::parser::ParseOptions;
+pub use crate::postgres::{IntervalStyle, Postgres};
+pub use crate::prometheus::Prometheus;
+pub use crate::rounding::RoundingMode;
+pub use crate::systemd::Systemd;
+pub use crate::unit::Unit;
+
+mod arithmetic;
+mod builder;
+mod clock;
+mod components;
+mod error;
+mod go;
+mod humanize;
+mod iso8601;
+mod locale;
+mod parser;
+mod postgres;
+mod prometheus;
+mod rounding;
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+pub mod serde;
+mod systemd;
+mod unit;
 
 const SECS_PER_MINUTES: i64 = 60;
 const SECS_PER_HOUR: i64 = 60 * SECS_PER_MINUTES;
 const SECS_PER_DAY: i64 = 24 * SECS_PER_HOUR;
 const SECS_PER_WEEK: i64 = 7 * SECS_PER_DAY;
 
-/// Errors returned by the different methods.
-#[derive(Copy, Clone, Debug)]
-pub enum DurationFlexError {
-	/// String format is not valid, e.g. `1y` (`y` is not supported).
-	InvalidFormat,
+const NANOS_PER_MICRO: u64 = 1_000;
+const NANOS_PER_MILLI: u64 = 1_000 * NANOS_PER_MICRO;
+const NANOS_PER_SEC: u64 = 1_000 * NANOS_PER_MILLI;
 
-	/// Value is out of range.
-	OutOfRange,
+/// Parses `value`, which must be a valid duration.
+#[cfg(test)]
+pub(crate) fn flex(value: &str) -> DurationFlex {
+	DurationFlex::try_from(value).unwrap()
 }
 
 /// Type to conveniently specify durations and interoperate with [`chrono::Duration`].
 ///
-/// The correct way of building this, is through one of the `from` methods.
+/// Can be built by parsing, from [`chrono::Duration`] or [`std::time::Duration`], with the `const` constructors (like
+/// [`DurationFlex::from_secs`] or [`DurationFlex::new`]) and constants (like [`DurationFlex::HOUR`]), or with
+/// [`DurationFlex::builder`].
+///
+/// Internally, the duration is stored as whole seconds plus the remaining nano-seconds, both truncated towards zero.
+/// This means [`DurationFlex::secs`] and [`DurationFlex::nanos`] never have opposite signs, e.g. `-1s500ms` is stored
+/// as `-1` seconds and `-500_000_000` nano-seconds.
 ///
 /// With the `clap` feature, can be used with [`clap`]:
 /// ```
@@ -180,54 +315,379 @@ impl validator::ValidateRange<i64> for DurationFlex {
 	}
 }
 
-static REGEX_STR: &str =
-	r"^((?P<weeks>\d+)w)?((?P<days>\d+)d)?((?P<hours>\d+)h)?((?P<minutes>\d+)m)?((?P<seconds>\d+)s)?$";
+impl DurationFlex {
+	/// One day, `1d`.
+	pub const DAY: DurationFlex = DurationFlex::from_days(1);
+	/// One hour, `1h`.
+	pub const HOUR: DurationFlex = DurationFlex::from_hours(1);
+	/// Largest duration, a bit over 292 billion years. Beyond the range of [`chrono::Duration`], so it can't be
+	/// converted to one, nor added to a [`DateTime`].
+	pub const MAX: DurationFlex = DurationFlex { secs: i64::MAX, nanos: 999_999_999 };
+	/// One micro-second, `1us`.
+	pub const MICROSECOND: DurationFlex = DurationFlex::from_micros(1);
+	/// One milli-second, `1ms`.
+	pub const MILLISECOND: DurationFlex = DurationFlex::from_millis(1);
+	/// Smallest (most negative) duration. Beyond the range of [`chrono::Duration`], so it can't be converted to one,
+	/// nor added to a [`DateTime`].
+	pub const MIN: DurationFlex = DurationFlex { secs: i64::MIN, nanos: -999_999_999 };
+	/// One minute, `1m`.
+	pub const MINUTE: DurationFlex = DurationFlex::from_minutes(1);
+	/// One nano-second, `1ns`.
+	pub const NANOSECOND: DurationFlex = DurationFlex::from_nanos(1);
+	/// One second, `1s`.
+	pub const SECOND: DurationFlex = DurationFlex::from_secs(1);
+	/// One week, `1w`.
+	pub const WEEK: DurationFlex = DurationFlex::from_weeks(1);
+	/// Zero duration, `0s`.
+	pub const ZERO: DurationFlex = DurationFlex { secs: 0, nanos: 0 };
+
+	/// Builds an instance from seconds and nano-seconds, with any signs. Nano-seconds beyond a second are carried to
+	/// the seconds, and the signs are normalized, e.g. `new(1, -500_000_000)` is `500ms`:
+	/// ```
+	/// use duration_flex::DurationFlex;
+	///
+	/// # pub fn main() {
+	/// const TIMEOUT: DurationFlex = DurationFlex::new(90, 1_500_000_000);
+	/// assert_eq!(TIMEOUT.to_string(), "1m31s500ms");
+	/// # }
+	/// ```
+	///
+	/// # Panics
+	///
+	/// If the result doesn't fit, e.g. `new(i64::MAX, 1_000_000_000)`.
+	pub const fn new(secs: i64, nanos: i32) -> Self {
+		match Self::from_total_nanos(secs as i128 * NANOS_PER_SEC as i128 + nanos as i128) {
+			Some(value) => value,
+			None => panic!("overflow in DurationFlex::new"),
+		}
+	}
 
-static REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(REGEX_STR).unwrap());
+	/// Builds an instance from whole seconds.
+	pub const fn from_secs(secs: i64) -> Self {
+		DurationFlex { secs, nanos: 0 }
+	}
 
-impl DurationFlex {
-	/// Whole seconds.
+	/// Builds an instance from milli-seconds.
+	pub const fn from_millis(millis: i64) -> Self {
+		Self::new(millis / 1_000, (millis % 1_000) as i32 * NANOS_PER_MILLI as i32)
+	}
+
+	/// Builds an instance from micro-seconds.
+	pub const fn from_micros(micros: i64) -> Self {
+		Self::new(micros / 1_000_000, (micros % 1_000_000) as i32 * NANOS_PER_MICRO as i32)
+	}
+
+	/// Builds an instance from nano-seconds.
+	pub const fn from_nanos(nanos: i64) -> Self {
+		Self::new(nanos / NANOS_PER_SEC as i64, (nanos % NANOS_PER_SEC as i64) as i32)
+	}
+
+	/// Builds an instance from minutes.
+	///
+	/// # Panics
+	///
+	/// If the result doesn't fit.
+	pub const fn from_minutes(minutes: i64) -> Self {
+		match minutes.checked_mul(SECS_PER_MINUTES) {
+			Some(secs) => Self::from_secs(secs),
+			None => panic!("overflow in DurationFlex::from_minutes"),
+		}
+	}
+
+	/// Builds an instance from hours.
+	///
+	/// # Panics
+	///
+	/// If the result doesn't fit.
+	pub const fn from_hours(hours: i64) -> Self {
+		match hours.checked_mul(SECS_PER_HOUR) {
+			Some(secs) => Self::from_secs(secs),
+			None => panic!("overflow in DurationFlex::from_hours"),
+		}
+	}
+
+	/// Builds an instance from days, of 24 hours.
+	///
+	/// # Panics
+	///
+	/// If the result doesn't fit.
+	pub const fn from_days(days: i64) -> Self {
+		match days.checked_mul(SECS_PER_DAY) {
+			Some(secs) => Self::from_secs(secs),
+			None => panic!("overflow in DurationFlex::from_days"),
+		}
+	}
+
+	/// Builds an instance from weeks, of 7 days.
+	///
+	/// # Panics
+	///
+	/// If the result doesn't fit.
+	pub const fn from_weeks(weeks: i64) -> Self {
+		match weeks.checked_mul(SECS_PER_WEEK) {
+			Some(secs) => Self::from_secs(secs),
+			None => panic!("overflow in DurationFlex::from_weeks"),
+		}
+	}
+
+	/// Builder, to set each unit separately, see [`Builder`]:
+	/// ```
+	/// use duration_flex::{DurationFlex, DurationFlexError};
+	///
+	/// # pub fn main() -> Result<(), DurationFlexError> {
+	/// let value = DurationFlex::builder().hours(1).minutes(30).build()?;
+	/// assert_eq!(value.to_string(), "1h30m");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn builder() -> Builder {
+		Builder::default()
+	}
+
+	/// Whole seconds, truncated towards zero.
 	pub fn secs(&self) -> i64 {
 		self.secs
 	}
 
-	/// Nano-seconds.
+	/// Nano-seconds, always with the same sign as [`DurationFlex::secs`] (unless one of them is zero).
 	pub fn nanos(&self) -> i32 {
 		self.nanos
 	}
 
-	fn de_component(r#match: Match) -> i64 {
-		r#match.as_str().parse().unwrap()
+	/// Seconds, with the fraction, e.g. `1.5` for `1s500ms`. Large durations lose nano-second precision.
+	pub fn as_secs_f64(&self) -> f64 {
+		self.secs as f64 + self.nanos as f64 / NANOS_PER_SEC as f64
 	}
 
-	fn ser_component(secs: &mut i64, component: &str, component_secs: i64, f: &mut Formatter<'_>) -> std::fmt::Result {
-		let value = *secs / component_secs;
-		*secs -= value * component_secs;
+	/// Builds an instance from fractional seconds, rounded to the nearest nano-second (half away from zero).
+	///
+	/// # Panics
+	///
+	/// If `secs` is NaN, infinite or doesn't fit, use [`DurationFlex::try_from_secs_f64`] to avoid it.
+	pub fn from_secs_f64(secs: f64) -> Self {
+		Self::try_from_secs_f64(secs).unwrap()
+	}
 
-		if value == 0 {
-			Ok(())
-		} else {
-			write!(f, "{}{}", value, component)
+	/// Builds an instance from fractional seconds, rounded to the nearest nano-second (half away from zero):
+	/// ```
+	/// use duration_flex::{DurationFlex, DurationFlexError};
+	///
+	/// # pub fn main() {
+	/// assert_eq!(DurationFlex::try_from_secs_f64(1.5).unwrap().to_string(), "1s500ms");
+	/// assert_eq!(DurationFlex::try_from_secs_f64(f64::NAN), Err(DurationFlexError::NotANumber));
+	/// assert_eq!(DurationFlex::try_from_secs_f64(1e20), Err(DurationFlexError::OutOfRange));
+	/// # }
+	/// ```
+	pub fn try_from_secs_f64(secs: f64) -> Result<Self, DurationFlexError> {
+		match secs {
+			_ if secs.is_nan() => Err(DurationFlexError::NotANumber),
+			_ if secs.is_infinite() => Err(DurationFlexError::Infinite),
+			_ => Self::from_secs_f64_rounded(secs).ok_or(DurationFlexError::OutOfRange),
 		}
 	}
-}
 
-impl Sub<Duration> for DurationFlex {
-	type Output = Duration;
+	/// Parser with configurable grammar relaxations, see [`ParseOptions`]. It starts as strict as
+	/// [`DurationFlex::try_from`].
+	pub fn parser() -> ParseOptions {
+		ParseOptions::default()
+	}
 
-	fn sub(self, rhs: Duration) -> Self::Output {
-		Duration::from(self) - rhs
+	/// Adapter to format for humans, e.g. `1 hour and 30 minutes`, with configurable separator, conjunction, maximum
+	/// amount of components and [`Abbreviation`]. The alternate flag of [`Display`] (`{:#}`) uses it with the defaults:
+	/// ```
+	/// use duration_flex::DurationFlex;
+	///
+	/// # pub fn main() {
+	/// let value = DurationFlex::try_from("1h30m5s").unwrap();
+	/// assert_eq!(format!("{:#}", value), "1 hour, 30 minutes and 5 seconds");
+	/// assert_eq!(value.humanize().max_components(2).to_string(), "1 hour and 30 minutes");
+	/// assert_eq!(value.humanize().conjunction(None).to_string(), "1 hour, 30 minutes, 5 seconds");
+	/// # }
+	/// ```
+	pub fn humanize<'a>(&self) -> Humanize<'a> {
+		Humanize::new(*self)
 	}
-}
 
-impl Add<Duration> for DurationFlex {
-	type Output = Duration;
+	/// Parses the clock format, `[-][d.]h:mm[:ss[.fraction]]`, e.g. `01:30:00`, `1:30` or `2.04:00:00` (.NET's
+	/// `TimeSpan` style).
+	///
+	/// Hours can be larger than a day, unless days are specified. Minutes and seconds must have 2 digits and be smaller
+	/// than 60. With the `clock` feature, [`DurationFlex::from_str`] falls back to this format.
+	pub fn parse_clock(input: &str) -> Result<Self, DurationFlexError> {
+		clock::parse(input)
+	}
+
+	/// Adapter to format like a clock, with configurable padding and precision:
+	/// ```
+	/// use duration_flex::DurationFlex;
+	///
+	/// # pub fn main() {
+	/// let value = DurationFlex::try_from("1d2h3m4s500ms").unwrap();
+	/// assert_eq!(value.clock().to_string(), "26:03:04.5");
+	/// assert_eq!(value.clock().days(true).precision(3).to_string(), "1.02:03:04.500");
+	/// # }
+	/// ```
+	pub fn clock(&self) -> Clock {
+		Clock::new(*self)
+	}
+
+	/// Parses an ISO 8601 duration, like `PT1H30M` or `-P2DT3H4M5.5S`.
+	///
+	/// Only the fixed-length designators are accepted: weeks (`W`), days (`D`), hours (`H`), minutes (`M`) and
+	/// seconds (`S`). Years and months are rejected with [`DurationFlexError::VariableLengthUnit`]. The smallest
+	/// component may have a fraction, using either `.` or `,`.
+	pub fn parse_iso8601(input: &str) -> Result<Self, DurationFlexError> {
+		iso8601::parse(input)
+	}
+
+	/// Adapter to format as an ISO 8601 duration, using days as the largest unit, e.g. `P1DT2H3M4.5S`:
+	/// ```
+	/// use duration_flex::DurationFlex;
+	///
+	/// # pub fn main() {
+	/// let value = DurationFlex::try_from("1d2h3m4s500ms").unwrap();
+	/// assert_eq!(value.iso8601().to_string(), "P1DT2H3M4.5S");
+	/// # }
+	/// ```
+	pub fn iso8601(&self) -> Iso8601 {
+		Iso8601(*self)
+	}
+
+	/// Parses a Go duration, exactly like Go's `time.ParseDuration`, e.g. `1h30m`, `1.5h`, `300ms` or `-2h45m`.
+	///
+	/// Components can be in any order, repeated and have a fraction, but there are no days nor weeks. Just like in Go,
+	/// the result must fit in an `i64` amount of nano-seconds (about 292 years).
+	pub fn parse_go(input: &str) -> Result<Self, DurationFlexError> {
+		go::parse(input)
+	}
+
+	/// Adapter to format exactly like Go's `time.Duration.String()`:
+	/// ```
+	/// use duration_flex::DurationFlex;
+	///
+	/// # pub fn main() {
+	/// assert_eq!(DurationFlex::try_from("1h").unwrap().go().to_string(), "1h0m0s");
+	/// assert_eq!(DurationFlex::try_from("1us500ns").unwrap().go().to_string(), "1.5µs");
+	/// # }
+	/// ```
+	pub fn go(&self) -> Go {
+		Go(*self)
+	}
+
+	/// Parses a PostgreSQL `interval`, as written by any of the [`IntervalStyle`]s, e.g. `1 day 02:03:04.5`,
+	/// `@ 1 hour 30 mins ago`, `1 2:03:04.5` or `P1DT2H3M4.5S`.
+	///
+	/// `style` is the `IntervalStyle` the input was written with, as it changes the meaning of a leading `-`: with
+	/// [`IntervalStyle::SqlStandard`], `-1 2:00:00` is minus 1 day and 2 hours, otherwise it is minus 1 day plus 2
+	/// hours. Only the fixed-length subset is supported, months and years are rejected with
+	/// [`DurationFlexError::VariableLengthUnit`].
+	pub fn parse_postgres(input: &str, style: IntervalStyle) -> Result<Self, DurationFlexError> {
+		postgres::parse(input, style)
+	}
 
-	fn add(self, rhs: Duration) -> Self::Output {
-		Duration::from(self) + rhs
+	/// Adapter to format like a PostgreSQL `interval` in the given `style`, with days as the largest unit:
+	/// ```
+	/// use duration_flex::{DurationFlex, IntervalStyle};
+	///
+	/// # pub fn main() {
+	/// let value = DurationFlex::try_from("1d2h3m4s500ms").unwrap();
+	/// assert_eq!(value.postgres(IntervalStyle::Postgres).to_string(), "1 day 02:03:04.5");
+	/// assert_eq!(
+	/// 	value.postgres(IntervalStyle::PostgresVerbose).to_string(),
+	/// 	"@ 1 day 2 hours 3 mins 4.5 secs"
+	/// );
+	/// assert_eq!(value.postgres(IntervalStyle::SqlStandard).to_string(), "1 2:03:04.5");
+	/// assert_eq!(value.postgres(IntervalStyle::Iso8601).to_string(), "P1DT2H3M4.5S");
+	/// # }
+	/// ```
+	pub fn postgres(&self, style: IntervalStyle) -> Postgres {
+		Postgres::new(*self, style)
+	}
+
+	/// Parses a Prometheus duration, exactly like Prometheus' `model.ParseDuration`, e.g. `5m`, `1h30m` or `1y`.
+	///
+	/// Components must be in order, from years (`y`, always 365 days) down to milli-seconds (`ms`), without fractions.
+	/// `0` is accepted without unit. Just like in Prometheus, the result must fit in an `i64` amount of nano-seconds.
+	pub fn parse_prometheus(input: &str) -> Result<Self, DurationFlexError> {
+		prometheus::parse(input)
+	}
+
+	/// Adapter to format exactly like Prometheus' `model.Duration.String()`, truncated to milli-seconds:
+	/// ```
+	/// use duration_flex::DurationFlex;
+	///
+	/// # pub fn main() {
+	/// assert_eq!(DurationFlex::try_from("14d").unwrap().prometheus().to_string(), "2w");
+	/// assert_eq!(DurationFlex::try_from("3w2d").unwrap().prometheus().to_string(), "23d");
+	/// # }
+	/// ```
+	pub fn prometheus(&self) -> Prometheus {
+		Prometheus(*self)
+	}
+
+	/// Parses a systemd.time(7) timespan, like `2min 30s`, `1h 5min`, `5 hours` or `infinity`.
+	///
+	/// Components are optionally separated by whitespace, can be in any order, repeated and have a fraction. All the
+	/// unit aliases documented by systemd are accepted, a number without unit is in seconds, months are 30.44 days and
+	/// years are 365.25 days. `infinity` is the largest duration. Negative durations are not accepted.
+	pub fn parse_systemd(input: &str) -> Result<Self, DurationFlexError> {
+		systemd::parse(input)
+	}
+
+	/// Adapter to format like systemd's timespans, e.g. `1h 5min` or `1.500000s`:
+	/// ```
+	/// use duration_flex::{DurationFlex, Unit};
+	///
+	/// # pub fn main() {
+	/// let value = DurationFlex::try_from("2m30s").unwrap();
+	/// assert_eq!(value.systemd().to_string(), "2min 30s");
+	///
+	/// let value = DurationFlex::try_from("1s500ms").unwrap();
+	/// assert_eq!(value.systemd().accuracy(Unit::Millisecond).to_string(), "1.500s");
+	/// # }
+	/// ```
+	pub fn systemd(&self) -> Systemd {
+		Systemd::new(*self)
+	}
+
+	/// Builds an instance from an amount of nano-seconds, or `None` if it doesn't fit.
+	pub(crate) const fn from_total_nanos(nanos: i128) -> Option<Self> {
+		let secs = nanos / NANOS_PER_SEC as i128;
+		if secs < i64::MIN as i128 || secs > i64::MAX as i128 {
+			return None;
+		}
+
+		Some(DurationFlex { secs: secs as i64, nanos: (nanos % NANOS_PER_SEC as i128) as i32 })
+	}
+
+	/// Builds an instance from seconds and nano-seconds with any sign or magnitude, or `None` if it doesn't fit.
+	pub(crate) fn from_secs_nanos(secs: i64, nanos: i64) -> Option<Self> {
+		Self::from_total_nanos(secs as i128 * NANOS_PER_SEC as i128 + nanos as i128)
+	}
+
+	/// Builds an instance from fractional seconds, rounded to the nearest nano-second (half away from zero), or
+	/// `None` if it is not finite or doesn't fit.
+	pub(crate) fn from_secs_f64_rounded(secs: f64) -> Option<Self> {
+		if !secs.is_finite() || secs.abs() >= i64::MAX as f64 {
+			return None;
+		}
+
+		let whole = secs.trunc();
+		let nanos = ((secs - whole) * NANOS_PER_SEC as f64).round();
+
+		Self::from_secs_nanos(whole as i64, nanos as i64)
+	}
+
+	/// Total amount of nano-seconds.
+	pub(crate) const fn total_nanos(&self) -> i128 {
+		self.secs as i128 * NANOS_PER_SEC as i128 + self.nanos as i128
 	}
 }
 
+/// # Panics
+///
+/// Panics if the duration is beyond the range of [`chrono::Duration`], or if the result is beyond the range of
+/// [`DateTime`], just like adding a [`chrono::Duration`].
 impl<T> Add<DateTime<T>> for DurationFlex
 where
 	T: TimeZone,
@@ -235,10 +695,14 @@ where
 	type Output = DateTime<T>;
 
 	fn add(self, rhs: DateTime<T>) -> Self::Output {
-		rhs + Duration::from(self)
+		rhs + Duration::try_from(self).expect("duration out of the range of chrono::Duration")
 	}
 }
 
+/// # Panics
+///
+/// Panics if the duration is beyond the range of [`chrono::Duration`], or if the result is beyond the range of
+/// [`DateTime`], just like adding a [`chrono::Duration`].
 impl<T> Add<DurationFlex> for DateTime<T>
 where
 	T: TimeZone,
@@ -246,7 +710,7 @@ where
 	type Output = DateTime<T>;
 
 	fn add(self, rhs: DurationFlex) -> Self::Output {
-		self + Duration::from(rhs)
+		self + Duration::try_from(rhs).expect("duration out of the range of chrono::Duration")
 	}
 }
 
@@ -254,22 +718,7 @@ impl TryFrom<&str> for DurationFlex {
 	type Error = DurationFlexError;
 
 	fn try_from(value: &str) -> Result<Self, Self::Error> {
-		let captures = REGEX.captures(value).ok_or(DurationFlexError::InvalidFormat)?;
-
-		let weeks = Duration::try_weeks(captures.name("weeks").map_or(0i64, Self::de_component))
-			.ok_or(DurationFlexError::OutOfRange)?;
-		let days = Duration::try_days(captures.name("days").map_or(0i64, Self::de_component))
-			.ok_or(DurationFlexError::OutOfRange)?;
-		let hours = Duration::try_hours(captures.name("hours").map_or(0i64, Self::de_component))
-			.ok_or(DurationFlexError::OutOfRange)?;
-		let minutes = Duration::try_minutes(captures.name("minutes").map_or(0i64, Self::de_component))
-			.ok_or(DurationFlexError::OutOfRange)?;
-		let seconds = Duration::try_seconds(captures.name("seconds").map_or(0i64, Self::de_component))
-			.ok_or(DurationFlexError::OutOfRange)?;
-
-		let duration = weeks + days + hours + minutes + seconds;
-
-		Ok(DurationFlex { secs: duration.num_seconds(), nanos: 0i32 })
+		ParseOptions::STRICT.parse(value)
 	}
 }
 
@@ -281,91 +730,199 @@ impl From<String> for DurationFlex {
 
 impl From<Duration> for DurationFlex {
 	fn from(value: Duration) -> Self {
-		DurationFlex { secs: value.num_seconds(), nanos: 0i32 }
+		// Both are truncated towards zero, so `secs` and `nanos` always share the same sign, e.g. -1.5s becomes
+		// `(-1, -500_000_000)` instead of chrono's internal `(-2, 500_000_000)`.
+		DurationFlex { secs: value.num_seconds(), nanos: value.subsec_nanos() }
 	}
 }
 
-impl From<DurationFlex> for Duration {
-	fn from(value: DurationFlex) -> Self {
-		Duration::try_seconds(value.secs()).unwrap() + Duration::nanoseconds(value.nanos() as i64)
+/// [`chrono::Duration`] has a narrower range (about 292 million years), so converting a duration outside of it fails
+/// with [`DurationFlexError::OutOfRange`].
+impl TryFrom<DurationFlex> for Duration {
+	type Error = DurationFlexError;
+
+	fn try_from(value: DurationFlex) -> Result<Self, Self::Error> {
+		Duration::try_seconds(value.secs())
+			.and_then(|secs| secs.checked_add(&Duration::nanoseconds(value.nanos() as i64)))
+			.ok_or(DurationFlexError::OutOfRange)
 	}
 }
 
-impl From<time::Duration> for DurationFlex {
-	fn from(value: time::Duration) -> Self {
-		DurationFlex { secs: value.as_secs() as i64, nanos: 0i32 }
+/// [`std::time::Duration`] has a wider positive range, so converting a duration longer than [`DurationFlex::MAX`] fails
+/// with [`DurationFlexError::OutOfRange`].
+impl TryFrom<time::Duration> for DurationFlex {
+	type Error = DurationFlexError;
+
+	fn try_from(value: time::Duration) -> Result<Self, Self::Error> {
+		let secs = i64::try_from(value.as_secs()).map_err(|_| DurationFlexError::OutOfRange)?;
+
+		Ok(DurationFlex { secs, nanos: value.subsec_nanos() as i32 })
 	}
 }
 
-impl From<DurationFlex> for time::Duration {
-	fn from(value: DurationFlex) -> Self {
-		time::Duration::from_secs(value.secs as u64).add(time::Duration::from_nanos(value.nanos as u64))
+/// [`std::time::Duration`] can't represent negative durations, so converting one fails with
+/// [`DurationFlexError::OutOfRange`].
+impl TryFrom<DurationFlex> for time::Duration {
+	type Error = DurationFlexError;
+
+	fn try_from(value: DurationFlex) -> Result<Self, Self::Error> {
+		if value.secs < 0 || value.nanos < 0 {
+			return Err(DurationFlexError::OutOfRange);
+		}
+
+		Ok(time::Duration::from_secs(value.secs as u64).add(time::Duration::from_nanos(value.nanos as u64)))
 	}
 }
 
+/// Formats as `1h30m`, or `1 hour and 30 minutes` with the alternate flag (`{:#}`), see [`DurationFlex::humanize`].
 impl Display for DurationFlex {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-		let mut secs = self.secs;
+		if f.alternate() {
+			return self.humanize().fmt(f);
+		}
 
-		Self::ser_component(&mut secs, "w", SECS_PER_WEEK, f)?;
-		Self::ser_component(&mut secs, "d", SECS_PER_DAY, f)?;
-		Self::ser_component(&mut secs, "h", SECS_PER_HOUR, f)?;
-		Self::ser_component(&mut secs, "m", SECS_PER_MINUTES, f)?;
-		Self::ser_component(&mut secs, "s", 1, f)
+		let total = self.total_nanos();
+		if total == 0 {
+			return f.write_str("0s");
+		} else if total < 0 {
+			f.write_str("-")?;
+		}
+
+		for (unit, amount) in self.components().iter().filter(|(_, amount)| *amount != 0) {
+			write!(f, "{}{}", amount.unsigned_abs(), unit.symbol())?;
+		}
+
+		Ok(())
 	}
 }
 
 #[cfg(feature = "serde")]
-impl<'de> Deserialize<'de> for DurationFlex {
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+static REGEX_MSG: &str = "a String with the format weeks (w), days (d), hours (h), minutes (m), seconds (s), \
+                          milliseconds (ms), microseconds (us) and/or nanoseconds (ns), in order, an amount of \
+                          seconds or a `{secs, nanos}` pair";
+
+/// Visitor of the human-readable representations, shared with the [`serde`](crate::serde) with-modules.
+#[cfg(feature = "serde")]
+pub(crate) struct DurationFlexVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for DurationFlexVisitor {
+	type Value = DurationFlex;
+
+	fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+		formatter.write_str(REGEX_MSG)
+	}
+
+	fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
 	where
-		D: Deserializer<'de>,
+		E: Error,
 	{
-		static REGEX_MSG: &str =
-			"a String with the format weeks (w), days (d), hours (h), minutes (m) and/or seconds (s), in order";
+		DurationFlex::try_from(v).map_err(|error| {
+			match error.suggestion() {
+				Some(suggestion) => {
+					Error::custom(format_args!("invalid value: {}, did you mean `{}`?", Unexpected::Str(v), suggestion))
+				},
+				None => Error::invalid_value(Unexpected::Str(v), &self),
+			}
+		})
+	}
 
-		struct DurationFlexVisitor;
+	fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		self.visit_str(v)
+	}
 
-		impl<'de> Visitor<'de> for DurationFlexVisitor {
-			type Value = DurationFlex;
+	fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		self.visit_str(v.as_str())
+	}
 
-			fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
-				formatter.write_str(REGEX_MSG)
-			}
+	fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		Ok(DurationFlex { secs: v, nanos: 0 })
+	}
 
-			fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-			where
-				E: Error,
-			{
-				match DurationFlex::try_from(v) {
-					Ok(value) => Ok(value),
-					Err(DurationFlexError::InvalidFormat) => Err(Error::invalid_value(Unexpected::Str(v), &self)),
-					Err(DurationFlexError::OutOfRange) => Err(Error::invalid_value(Unexpected::Str(v), &self)),
-				}
-			}
+	fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		let secs = i64::try_from(v).map_err(|_| Error::invalid_value(Unexpected::Unsigned(v), &self))?;
 
-			fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
-			where
-				E: Error,
-			{
-				self.visit_str(v)
-			}
+		Ok(DurationFlex { secs, nanos: 0 })
+	}
+
+	fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		DurationFlex::from_secs_f64_rounded(v).ok_or_else(|| Error::invalid_value(Unexpected::Float(v), &self))
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		let secs: i64 = seq.next_element()?.ok_or_else(|| Error::invalid_length(0, &self))?;
+		let nanos: i64 = seq.next_element()?.ok_or_else(|| Error::invalid_length(1, &self))?;
 
-			fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
-			where
-				E: Error,
-			{
-				match DurationFlex::try_from(v.as_str()) {
-					Ok(value) => Ok(value),
-					Err(DurationFlexError::InvalidFormat) => {
-						Err(Error::invalid_value(Unexpected::Str(v.as_str()), &self))
-					},
-					Err(DurationFlexError::OutOfRange) => Err(Error::invalid_value(Unexpected::Str(v.as_str()), &self)),
-				}
+		DurationFlex::from_secs_nanos(secs, nanos).ok_or_else(|| {
+			Error::custom(format_args!("duration of {} seconds and {} nanoseconds is out of range", secs, nanos))
+		})
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'de>,
+	{
+		let mut secs: Option<i64> = None;
+		let mut nanos: Option<i64> = None;
+
+		while let Some(key) = map.next_key::<String>()? {
+			match key.as_str() {
+				"secs" if secs.is_some() => return Err(Error::duplicate_field("secs")),
+				"secs" => secs = Some(map.next_value()?),
+				"nanos" if nanos.is_some() => return Err(Error::duplicate_field("nanos")),
+				"nanos" => nanos = Some(map.next_value()?),
+				_ => return Err(Error::unknown_field(key.as_str(), &["secs", "nanos"])),
 			}
 		}
 
-		deserializer.deserialize_string(DurationFlexVisitor)
+		let secs = secs.ok_or_else(|| Error::missing_field("secs"))?;
+		let nanos = nanos.ok_or_else(|| Error::missing_field("nanos"))?;
+
+		DurationFlex::from_secs_nanos(secs, nanos).ok_or_else(|| {
+			Error::custom(format_args!("duration of {} seconds and {} nanoseconds is out of range", secs, nanos))
+		})
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for DurationFlex {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		if deserializer.is_human_readable() {
+			deserializer.deserialize_any(DurationFlexVisitor)
+		} else {
+			// Only the canonical pair written by `serialize` is accepted, so each value has a single encoding.
+			let (secs, nanos) = <(i64, i32)>::deserialize(deserializer)?;
+			if nanos.unsigned_abs() as u64 >= NANOS_PER_SEC || secs.signum() * (nanos.signum() as i64) < 0 {
+				return Err(Error::custom(format_args!(
+					"invalid duration of {} seconds and {} nanoseconds, nanoseconds must be less than a second and \
+					 have the sign of seconds",
+					secs, nanos
+				)));
+			}
+
+			Ok(DurationFlex { secs, nanos })
+		}
 	}
 }
 
@@ -375,7 +932,21 @@ impl Serialize for DurationFlex {
 	where
 		S: Serializer,
 	{
-		serializer.serialize_str(format!("{}", self).as_str())
+		if serializer.is_human_readable() {
+			serializer.collect_str(self)
+		} else {
+			(self.secs, self.nanos).serialize(serializer)
+		}
+	}
+}
+
+/// Parses arguments with [`DurationFlex::from_str`], so errors (and suggestions) are reported by [`clap`].
+#[cfg(feature = "clap")]
+impl clap::builder::ValueParserFactory for DurationFlex {
+	type Parser = fn(&str) -> Result<DurationFlex, DurationFlexError>;
+
+	fn value_parser() -> Self::Parser {
+		DurationFlex::from_str
 	}
 }
 
@@ -393,19 +964,33 @@ impl From<DurationFlex> for OsStr {
 	}
 }
 
+/// With the `clock` feature, inputs with a `:` that are not valid durations are parsed with
+/// [`DurationFlex::parse_clock`], e.g. `01:30:00`.
 impl FromStr for DurationFlex {
 	type Err = DurationFlexError;
 
+	#[cfg(not(feature = "clock"))]
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		DurationFlex::try_from(s)
 	}
+
+	#[cfg(feature = "clock")]
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		DurationFlex::try_from(s)
+			.or_else(|error| if s.contains(':') { DurationFlex::parse_clock(s) } else { Err(error) })
+	}
 }
 
 #[cfg(test)]
 mod test {
 
-	use serde::{Deserialize, Serialize};
-	use serde_test::{assert_de_tokens, assert_ser_tokens, Token};
+	use ::serde::{Deserialize, Serialize};
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+	use proptest::sample::select;
+	use serde_test::{
+		assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens, Compact, Configure, Readable, Token,
+	};
 
 	use super::*;
 
@@ -425,6 +1010,311 @@ mod test {
 
 		let value = DurationFlex::try_from("5s5d");
 		assert!(value.is_err());
+
+		let value = DurationFlex::try_from("250ms").unwrap();
+		assert_eq!(value.secs(), 0);
+		assert_eq!(value.nanos(), 250_000_000);
+
+		let value = DurationFlex::try_from("1s2ms3us4ns").unwrap();
+		assert_eq!(value.secs(), 1);
+		assert_eq!(value.nanos(), 2_003_004);
+
+		let value = DurationFlex::try_from("3µs").unwrap();
+		assert_eq!(value.secs(), 0);
+		assert_eq!(value.nanos(), 3_000);
+
+		let value = DurationFlex::try_from("1m1500ms").unwrap();
+		assert_eq!(value.secs(), SECS_PER_MINUTES + 1);
+		assert_eq!(value.nanos(), 500_000_000);
+
+		let value = DurationFlex::try_from("5ns5ms");
+		assert!(value.is_err());
+
+		let value = DurationFlex::try_from("-5m").unwrap();
+		assert_eq!(value.secs(), -5 * SECS_PER_MINUTES);
+		assert_eq!(value.nanos(), 0);
+
+		let value = DurationFlex::try_from("+5m").unwrap();
+		assert_eq!(value.secs(), 5 * SECS_PER_MINUTES);
+		assert_eq!(value.nanos(), 0);
+
+		let value = DurationFlex::try_from("-1s250ms").unwrap();
+		assert_eq!(value.secs(), -1);
+		assert_eq!(value.nanos(), -250_000_000);
+
+		let value = DurationFlex::try_from("-250ms").unwrap();
+		assert_eq!(value.secs(), 0);
+		assert_eq!(value.nanos(), -250_000_000);
+
+		let value = DurationFlex::try_from("1h-5m");
+		assert!(value.is_err());
+
+		let value = DurationFlex::try_from("--5m");
+		assert!(value.is_err());
+	}
+
+	#[test]
+	fn de_out_of_range() {
+		let value = DurationFlex::try_from("99999999999999999999s");
+		assert_eq!(
+			value,
+			Err(DurationFlexError::ComponentOverflow { offset: 0, text: "99999999999999999999s".to_string() })
+		);
+
+		let value = DurationFlex::try_from("1w9223372036854775807h");
+		assert_eq!(
+			value,
+			Err(DurationFlexError::ComponentOverflow { offset: 2, text: "9223372036854775807h".to_string() })
+		);
+
+		let value = DurationFlex::try_from("9223372036854775807s1000ms");
+		assert_eq!(value, Err(DurationFlexError::OutOfRange));
+
+		let value = DurationFlex::try_from("9223372036854775807s999ms").unwrap();
+		assert_eq!(value.secs(), i64::MAX);
+		assert_eq!(value.nanos(), 999_000_000);
+
+		let value = DurationFlex::try_from("-9223372036854775807ms").unwrap();
+		assert_eq!(value.secs(), -9223372036854775);
+		assert_eq!(value.nanos(), -807_000_000);
+
+		let value = DurationFlex::try_from("-9223372036854775808s").unwrap();
+		assert_eq!(value.secs(), i64::MIN);
+		assert_eq!(value.nanos(), 0);
+
+		let value = DurationFlex::try_from("-9223372036854775808s999ms999us999ns");
+		assert_eq!(value, Ok(DurationFlex::MIN));
+
+		let value = DurationFlex::try_from("9223372036854775808s");
+		assert_eq!(
+			value,
+			Err(DurationFlexError::ComponentOverflow { offset: 0, text: "9223372036854775808s".to_string() })
+		);
+
+		for value in [DurationFlex::MIN, DurationFlex::MAX] {
+			assert_eq!(DurationFlex::try_from(value.to_string().as_str()), Ok(value));
+		}
+	}
+
+	#[test]
+	fn de_errors() {
+		assert_eq!(DurationFlex::try_from(""), Err(DurationFlexError::EmptyInput { grammar: Grammar::Native }));
+		assert_eq!(DurationFlex::try_from("-"), Err(DurationFlexError::EmptyInput { grammar: Grammar::Native }));
+
+		let value = DurationFlex::try_from("1h30x");
+		assert_eq!(
+			value,
+			Err(DurationFlexError::UnknownUnit {
+				offset: 4,
+				unit: "x".to_string(),
+				suggestion: None,
+				grammar: Grammar::Native
+			})
+		);
+
+		let value = DurationFlex::try_from("5s5d");
+		assert_eq!(
+			value,
+			Err(DurationFlexError::UnitOutOfOrder {
+				offset: 3,
+				unit: "d".to_string(),
+				suggestion: Some("5d5s".to_string()),
+				grammar: Grammar::Native
+			})
+		);
+
+		let value = DurationFlex::try_from("1h1h");
+		assert_eq!(
+			value,
+			Err(DurationFlexError::DuplicatedUnit {
+				offset: 3,
+				unit: "h".to_string(),
+				suggestion: Some("2h".to_string())
+			})
+		);
+
+		let value = DurationFlex::try_from("1h30");
+		assert_eq!(
+			value,
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 2,
+				text: "30".to_string(),
+				suggestion: None,
+				grammar: Grammar::Native
+			})
+		);
+
+		let value = DurationFlex::try_from("1h 30m");
+		assert_eq!(
+			value,
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 2,
+				text: " 30m".to_string(),
+				suggestion: Some("1h30m".to_string()),
+				grammar: Grammar::Native
+			})
+		);
+
+		let value = DurationFlex::try_from("1h30x").unwrap_err().to_string();
+		assert_eq!(value, "unknown unit `x` at offset 4");
+	}
+
+	#[test]
+	fn de_suggestions() {
+		let suggestion = |value: &str| DurationFlex::try_from(value).unwrap_err().suggestion().map(str::to_string);
+
+		assert_eq!(suggestion("5min").as_deref(), Some("5m"));
+		assert_eq!(suggestion("3hrs").as_deref(), Some("3h"));
+		assert_eq!(suggestion("2D").as_deref(), Some("2d"));
+		assert_eq!(suggestion("10sec").as_deref(), Some("10s"));
+		assert_eq!(suggestion("5s5d").as_deref(), Some("5d5s"));
+		assert_eq!(suggestion("-1 Hour 30 Minutes").as_deref(), Some("-1h30m"));
+		assert_eq!(suggestion("250 millis").as_deref(), Some("250ms"));
+		assert_eq!(suggestion("5y").as_deref(), None);
+		assert_eq!(suggestion("5").as_deref(), None);
+
+		let value = DurationFlex::try_from("5min").unwrap_err().to_string();
+		assert_eq!(value, "unknown unit `min` at offset 1, did you mean `5m`?");
+	}
+
+	#[test]
+	fn de_parser() {
+		let parser = DurationFlex::parser();
+		assert_eq!(parser.parse("1h30m"), DurationFlex::try_from("1h30m"));
+		assert!(parser.parse("1h 30m").is_err());
+		assert!(parser.parse("30").is_err());
+
+		let parser = DurationFlex::parser().allow_whitespace(true);
+		assert_eq!(parser.parse(" - 1h 30 m ").unwrap().to_string(), "-1h30m");
+		assert!(parser.parse("1H").is_err());
+
+		let parser = DurationFlex::parser().case_insensitive(true);
+		assert_eq!(parser.parse("1H30M5MS").unwrap().to_string(), "1h30m5ms");
+		assert!(parser.parse("1hour").is_err());
+
+		let parser = DurationFlex::parser().any_order(true);
+		assert_eq!(parser.parse("30m1h").unwrap().to_string(), "1h30m");
+		assert!(parser.parse("1h1h").is_err());
+
+		let parser = DurationFlex::parser().sum_repeated(true);
+		assert_eq!(parser.parse("1h1h30m").unwrap().to_string(), "2h30m");
+		assert!(parser.parse("1h30m1h").is_err());
+
+		let parser = DurationFlex::parser().long_units(true);
+		assert_eq!(parser.parse("1hour30mins").unwrap().to_string(), "1h30m");
+		assert!(parser.parse("1Hour").is_err());
+
+		let parser = DurationFlex::parser().default_unit(Unit::Millisecond);
+		assert_eq!(parser.parse("1500").unwrap().to_string(), "1s500ms");
+		assert_eq!(parser.parse("-1500").unwrap().to_string(), "-1s500ms");
+		assert!(parser.parse("1s500").is_err());
+
+		let error = DurationFlex::parser().parse("5min").unwrap_err();
+		assert_eq!(error.suggestion(), Some("5m"));
+	}
+
+	#[test]
+	fn de_error_report() {
+		let error = DurationFlex::try_from("1h30x").unwrap_err();
+		assert_eq!(error.span(), Some(4..5));
+		assert_eq!(
+			error.report("1h30x").to_string(),
+			"error: unknown unit `x` at offset 4\n  |\n  | 1h30x\n  |     ^ unknown unit\n  |\n  = help: valid units \
+			 are w, d, h, m, s, ms, us and ns\n"
+		);
+
+		let error = DurationFlex::try_from("µ5s5d").unwrap_err();
+		assert_eq!(error.span(), Some(0..6));
+		assert_eq!(error.report("µ5s5d").to_string().lines().nth(3), Some("  | ^^^^^ not a component"));
+
+		let error = DurationFlex::try_from("").unwrap_err();
+		assert_eq!(error.span(), None);
+		assert_eq!(
+			error.report("").to_string(),
+			"error: empty duration\n  |\n  = help: specify at least one component, like `1h30m`\n"
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "miette")]
+	fn de_error_diagnostic() {
+		use miette::{Diagnostic, LabeledSpan, NarratableReportHandler};
+
+		let error = DurationFlex::try_from("1h30x").unwrap_err();
+		assert_eq!(error.code().unwrap().to_string(), "duration_flex::unknown_unit");
+		assert_eq!(Diagnostic::help(&error).unwrap().to_string(), "valid units are w, d, h, m, s, ms, us and ns");
+		assert_eq!(
+			error.labels().unwrap().collect::<Vec<_>>(),
+			[LabeledSpan::new_with_span(Some("unknown unit".to_string()), 4..5)]
+		);
+
+		let report = miette::Report::new(error).with_source_code("1h30x");
+		let mut rendered = String::new();
+		NarratableReportHandler::new().render_report(&mut rendered, report.as_ref()).unwrap();
+		assert!(rendered.contains("unknown unit `x` at offset 4"), "{}", rendered);
+		assert!(rendered.contains("label at line 1, column 5: unknown unit"), "{}", rendered);
+
+		let error = DurationFlex::try_from("").unwrap_err();
+		assert_eq!(error.code().unwrap().to_string(), "duration_flex::empty_input");
+		assert!(error.labels().is_none());
+	}
+
+	/// Pieces of inputs for the parser, biased towards valid components, so both success and failure paths are
+	/// exercised.
+	const ALPHABET: &[&str] = &[
+		"0",
+		"1",
+		"5",
+		"9",
+		"99999999999",
+		"9223372036854775807",
+		"9223372036854775808",
+		"w",
+		"d",
+		"h",
+		"m",
+		"s",
+		"ms",
+		"us",
+		"µs",
+		"ns",
+		"-",
+		"+",
+		" ",
+		"x",
+		"é",
+	];
+
+	// See `fuzz/` for the coverage-guided version.
+	proptest! {
+		/// Arbitrary strings never panic the parsers.
+		#[test]
+		fn de_never_panics(input in any::<String>()) {
+			let _ = DurationFlex::try_from(input.as_str());
+			let _ = DurationFlex::parser()
+				.allow_whitespace(true)
+				.case_insensitive(true)
+				.any_order(true)
+				.sum_repeated(true)
+				.long_units(true)
+				.default_unit(Unit::Second)
+				.parse(&input);
+		}
+
+		/// Strings made of components never panic, and whatever parses round-trips through `Display`.
+		#[test]
+		fn de_components_never_panic(input in vec(select(ALPHABET), 0..12).prop_map(|parts| parts.concat())) {
+			if let Ok(value) = DurationFlex::try_from(input.as_str()) {
+				prop_assert_eq!(DurationFlex::try_from(value.to_string().as_str()), Ok(value));
+			}
+		}
+
+		/// Any duration round-trips through `Display` and parsing.
+		#[test]
+		fn ser_de_round_trip(total in DurationFlex::MIN.total_nanos()..=DurationFlex::MAX.total_nanos()) {
+			let value = DurationFlex::from_total_nanos(total).unwrap();
+			prop_assert_eq!(DurationFlex::try_from(value.to_string().as_str()), Ok(value));
+		}
 	}
 
 	#[test]
@@ -443,42 +1333,230 @@ mod test {
 
 		let value = DurationFlex::try_from("1w8d3h4m3605s").unwrap().to_string();
 		assert_eq!(value, "2w1d4h4m5s");
+
+		let value = DurationFlex::try_from("1s250ms").unwrap().to_string();
+		assert_eq!(value, "1s250ms");
+
+		let value = DurationFlex::try_from("1h2ms3µs4ns").unwrap().to_string();
+		assert_eq!(value, "1h2ms3us4ns");
+
+		let value = DurationFlex::try_from("2500us").unwrap().to_string();
+		assert_eq!(value, "2ms500us");
+
+		let value = DurationFlex::try_from("-1h30m").unwrap().to_string();
+		assert_eq!(value, "-1h30m");
+
+		let value = DurationFlex::try_from("-1s250ms").unwrap().to_string();
+		assert_eq!(value, "-1s250ms");
+
+		let value = DurationFlex::try_from("+1h").unwrap().to_string();
+		assert_eq!(value, "1h");
+
+		let value = DurationFlex::try_from("-0s").unwrap().to_string();
+		assert_eq!(value, "0s");
+
+		let value = DurationFlex::from(Duration::nanoseconds(-5_400_000_000_000)).to_string();
+		assert_eq!(value, "-1h30m");
+
+		let value = DurationFlex::from(Duration::nanoseconds(-250)).to_string();
+		assert_eq!(value, "-250ns");
+	}
+
+	#[test]
+	fn from_chrono() {
+		let duration = Duration::try_seconds(90).unwrap() + Duration::nanoseconds(250);
+		let value = DurationFlex::from(duration);
+		assert_eq!(value.secs(), 90);
+		assert_eq!(value.nanos(), 250);
+		assert_eq!(Duration::try_from(value), Ok(duration));
+
+		let duration = Duration::nanoseconds(-1_500_000_000);
+		let value = DurationFlex::from(duration);
+		assert_eq!(value.secs(), -1);
+		assert_eq!(value.nanos(), -500_000_000);
+		assert_eq!(Duration::try_from(value), Ok(duration));
+
+		let duration = Duration::nanoseconds(-250);
+		let value = DurationFlex::from(duration);
+		assert_eq!(value.secs(), 0);
+		assert_eq!(value.nanos(), -250);
+		assert_eq!(Duration::try_from(value), Ok(duration));
+
+		assert_eq!(DurationFlex::from(Duration::MAX), flex("15250284452w3d7h12m55s807ms"));
+		assert_eq!(Duration::try_from(DurationFlex::MAX), Err(DurationFlexError::OutOfRange));
+		assert_eq!(Duration::try_from(DurationFlex::MIN), Err(DurationFlexError::OutOfRange));
+	}
+
+	#[test]
+	fn from_std() {
+		let duration = time::Duration::new(90, 250);
+		let value = DurationFlex::try_from(duration).unwrap();
+		assert_eq!(value.secs(), 90);
+		assert_eq!(value.nanos(), 250);
+		assert_eq!(time::Duration::try_from(value), Ok(duration));
+
+		let duration = time::Duration::from_nanos(999_999_999);
+		let value = DurationFlex::try_from(duration).unwrap();
+		assert_eq!(value.secs(), 0);
+		assert_eq!(value.nanos(), 999_999_999);
+		assert_eq!(time::Duration::try_from(value), Ok(duration));
+
+		let duration = time::Duration::new(i64::MAX as u64, 999_999_999);
+		assert_eq!(DurationFlex::try_from(duration), Ok(DurationFlex::MAX));
+		assert_eq!(time::Duration::try_from(DurationFlex::MAX), Ok(duration));
+		assert_eq!(time::Duration::try_from(flex("-1ns")), Err(DurationFlexError::OutOfRange));
+
+		assert_eq!(DurationFlex::try_from(time::Duration::MAX), Err(DurationFlexError::OutOfRange));
+		assert_eq!(
+			DurationFlex::try_from(time::Duration::new(i64::MAX as u64 + 1, 0)),
+			Err(DurationFlexError::OutOfRange)
+		);
+	}
+
+	#[test]
+	fn constructors() {
+		assert_eq!(DurationFlex::new(90, 250), DurationFlex { secs: 90, nanos: 250 });
+		assert_eq!(DurationFlex::new(1, 1_500_000_000), DurationFlex::try_from("2s500ms").unwrap());
+		assert_eq!(DurationFlex::new(1, -500_000_000), DurationFlex::try_from("500ms").unwrap());
+		assert_eq!(DurationFlex::new(-1, 500_000_000), DurationFlex::try_from("-500ms").unwrap());
+		assert_eq!(DurationFlex::new(0, i32::MIN), DurationFlex { secs: -2, nanos: -147_483_648 });
+		assert_eq!(DurationFlex::new(i64::MAX, 999_999_999), DurationFlex::MAX);
+		assert_eq!(DurationFlex::new(i64::MIN, -999_999_999), DurationFlex::MIN);
+
+		assert_eq!(DurationFlex::from_secs(-90), DurationFlex::try_from("-1m30s").unwrap());
+		assert_eq!(DurationFlex::from_millis(1_500), DurationFlex::try_from("1s500ms").unwrap());
+		assert_eq!(DurationFlex::from_millis(-1_500), DurationFlex::try_from("-1s500ms").unwrap());
+		assert_eq!(DurationFlex::from_micros(1_000_001), DurationFlex::try_from("1s1us").unwrap());
+		assert_eq!(DurationFlex::from_nanos(i64::MIN), DurationFlex { secs: -9_223_372_036, nanos: -854_775_808 });
+		assert_eq!(DurationFlex::from_minutes(90), DurationFlex::try_from("1h30m").unwrap());
+		assert_eq!(DurationFlex::from_hours(36), DurationFlex::try_from("1d12h").unwrap());
+		assert_eq!(DurationFlex::from_days(-8), DurationFlex::try_from("-1w1d").unwrap());
+		assert_eq!(DurationFlex::from_weeks(2), DurationFlex::try_from("2w").unwrap());
+	}
+
+	#[test]
+	#[should_panic(expected = "overflow in DurationFlex::new")]
+	fn new_overflow() {
+		let _ = DurationFlex::new(i64::MAX, 1_000_000_000);
+	}
+
+	#[test]
+	#[should_panic(expected = "overflow in DurationFlex::from_weeks")]
+	fn from_weeks_overflow() {
+		let _ = DurationFlex::from_weeks(i64::MAX);
+	}
+
+	#[test]
+	fn constants() {
+		const TIMEOUT: DurationFlex = DurationFlex::from_minutes(5);
+
+		assert_eq!(TIMEOUT.to_string(), "5m");
+		assert_eq!(DurationFlex::ZERO.to_string(), "0s");
+		assert_eq!(DurationFlex::NANOSECOND.to_string(), "1ns");
+		assert_eq!(DurationFlex::MICROSECOND.to_string(), "1us");
+		assert_eq!(DurationFlex::MILLISECOND.to_string(), "1ms");
+		assert_eq!(DurationFlex::SECOND.to_string(), "1s");
+		assert_eq!(DurationFlex::MINUTE.to_string(), "1m");
+		assert_eq!(DurationFlex::HOUR.to_string(), "1h");
+		assert_eq!(DurationFlex::DAY.to_string(), "1d");
+		assert_eq!(DurationFlex::WEEK.to_string(), "1w");
+		assert_eq!(DurationFlex::MAX.checked_add(DurationFlex::NANOSECOND), None);
+		assert_eq!(DurationFlex::MIN.checked_sub(DurationFlex::NANOSECOND), None);
 	}
 
 	#[test]
 	fn deserialize_nums() {
 		let value = DurationFlex::try_from("1w2d").unwrap();
-		assert_de_tokens(&value, &[Token::Str("1w2d")]);
+		assert_de_tokens(&value.readable(), &[Token::Str("1w2d")]);
 
 		let value = DurationFlex::try_from("1w2d3h4m5s").unwrap();
-		assert_de_tokens(&value, &[Token::Str("1w2d3h4m5s")]);
+		assert_de_tokens(&value.readable(), &[Token::Str("1w2d3h4m5s")]);
 
 		let value = DurationFlex::try_from("5s").unwrap();
-		assert_de_tokens(&value, &[Token::Str("5s")]);
+		assert_de_tokens(&value.readable(), &[Token::Str("5s")]);
 
 		let value = DurationFlex::try_from("1w8d3h4m5s").unwrap();
-		assert_de_tokens(&value, &[Token::Str("2w1d3h4m5s")]);
+		assert_de_tokens(&value.readable(), &[Token::Str("2w1d3h4m5s")]);
 
 		let value = DurationFlex::try_from("1w8d3h4m3605s").unwrap();
-		assert_de_tokens(&value, &[Token::Str("2w1d4h4m5s")]);
+		assert_de_tokens(&value.readable(), &[Token::Str("2w1d4h4m5s")]);
 	}
 
 	#[test]
 	fn serialize() {
 		let value = DurationFlex::try_from("1w2d").unwrap();
-		assert_ser_tokens(&value, &[Token::Str("1w2d")]);
+		assert_ser_tokens(&value.readable(), &[Token::Str("1w2d")]);
 
 		let value = DurationFlex::try_from("1w2d3h4m5s").unwrap();
-		assert_ser_tokens(&value, &[Token::Str("1w2d3h4m5s")]);
+		assert_ser_tokens(&value.readable(), &[Token::Str("1w2d3h4m5s")]);
 
 		let value = DurationFlex::try_from("5s").unwrap();
-		assert_ser_tokens(&value, &[Token::Str("5s")]);
+		assert_ser_tokens(&value.readable(), &[Token::Str("5s")]);
 
 		let value = DurationFlex::try_from("1w8d3h4m5s").unwrap();
-		assert_ser_tokens(&value, &[Token::Str("2w1d3h4m5s")]);
+		assert_ser_tokens(&value.readable(), &[Token::Str("2w1d3h4m5s")]);
 
 		let value = DurationFlex::try_from("1w8d3h4m3605s").unwrap();
-		assert_ser_tokens(&value, &[Token::Str("2w1d4h4m5s")]);
+		assert_ser_tokens(&value.readable(), &[Token::Str("2w1d4h4m5s")]);
+	}
+
+	#[test]
+	fn compact() {
+		let value = DurationFlex::try_from("1m30s500ms").unwrap();
+		assert_tokens(
+			&value.compact(),
+			&[Token::Tuple { len: 2 }, Token::I64(90), Token::I32(500_000_000), Token::TupleEnd],
+		);
+		assert_tokens(&value.readable(), &[Token::Str("1m30s500ms")]);
+
+		let value = DurationFlex::try_from("-1s500ms").unwrap();
+		assert_tokens(
+			&value.compact(),
+			&[Token::Tuple { len: 2 }, Token::I64(-1), Token::I32(-500_000_000), Token::TupleEnd],
+		);
+
+		assert_de_tokens_error::<Compact<DurationFlex>>(
+			&[Token::Tuple { len: 2 }, Token::I64(-2), Token::I32(500_000_000), Token::TupleEnd],
+			"invalid duration of -2 seconds and 500000000 nanoseconds, nanoseconds must be less than a second and \
+			 have the sign of seconds",
+		);
+
+		assert_de_tokens_error::<Compact<DurationFlex>>(
+			&[Token::Tuple { len: 2 }, Token::I64(i64::MAX), Token::I32(1_000_000_000), Token::TupleEnd],
+			"invalid duration of 9223372036854775807 seconds and 1000000000 nanoseconds, nanoseconds must be less \
+			 than a second and have the sign of seconds",
+		);
+	}
+
+	#[test]
+	fn binary_formats() {
+		let config = bincode::config::standard();
+		let values = [
+			DurationFlex::ZERO,
+			DurationFlex::try_from("1m30s500ms").unwrap(),
+			DurationFlex::try_from("-1s500ms").unwrap(),
+			DurationFlex::try_from("-1ns").unwrap(),
+			DurationFlex::MAX,
+			DurationFlex::MIN,
+		];
+
+		for value in values {
+			let bytes = bincode::serde::encode_to_vec(value, config).unwrap();
+			assert_eq!(bincode::serde::decode_from_slice(&bytes, config).unwrap(), (value, bytes.len()), "{}", value);
+
+			let bytes = postcard::to_allocvec(&value).unwrap();
+			assert_eq!(postcard::from_bytes::<DurationFlex>(&bytes).unwrap(), value, "{}", value);
+		}
+
+		for invalid in
+			[(-2i64, 500_000_000i32), (1, -1), (0, 1_000_000_000), (i64::MAX, i32::MAX), (i64::MIN, i32::MIN)]
+		{
+			let bytes = bincode::serde::encode_to_vec(invalid, config).unwrap();
+			assert!(bincode::serde::decode_from_slice::<DurationFlex, _>(&bytes, config).is_err(), "{:?}", invalid);
+
+			let bytes = postcard::to_allocvec(&invalid).unwrap();
+			assert!(postcard::from_bytes::<DurationFlex>(&bytes).is_err(), "{:?}", invalid);
+		}
 	}
 
 	#[test]
@@ -491,11 +1569,134 @@ mod test {
 		let value = SomeStruct { duration: Duration::try_weeks(1).unwrap().into() };
 
 		assert_ser_tokens(
-			&value,
+			&value.readable(),
 			&[Token::Struct { name: "SomeStruct", len: 1 }, Token::Str("duration"), Token::Str("1w"), Token::StructEnd],
 		);
 	}
 
+	#[test]
+	fn deserialize_numbers() {
+		let value = DurationFlex::try_from("30s").unwrap();
+		assert_de_tokens(&value.readable(), &[Token::I64(30)]);
+		assert_de_tokens(&value.readable(), &[Token::U64(30)]);
+		assert_de_tokens(&value.readable(), &[Token::I32(30)]);
+		assert_de_tokens(&value.readable(), &[Token::F64(30.0)]);
+
+		let value = DurationFlex::try_from("-1m30s").unwrap();
+		assert_de_tokens(&value.readable(), &[Token::I64(-90)]);
+
+		let value = DurationFlex::try_from("1s500ms").unwrap();
+		assert_de_tokens(&value.readable(), &[Token::F64(1.5)]);
+
+		let value = DurationFlex::try_from("-2s250ms").unwrap();
+		assert_de_tokens(&value.readable(), &[Token::F32(-2.25)]);
+
+		assert_de_tokens_error::<Readable<DurationFlex>>(
+			&[Token::U64(u64::MAX)],
+			"invalid value: integer `18446744073709551615`, expected a String with the format weeks (w), days (d), \
+			 hours (h), minutes (m), seconds (s), milliseconds (ms), microseconds (us) and/or nanoseconds (ns), in \
+			 order, an amount of seconds or a `{secs, nanos}` pair",
+		);
+
+		assert_de_tokens_error::<Readable<DurationFlex>>(
+			&[Token::F64(f64::NAN)],
+			"invalid value: floating point `NaN`, expected a String with the format weeks (w), days (d), hours (h), \
+			 minutes (m), seconds (s), milliseconds (ms), microseconds (us) and/or nanoseconds (ns), in order, an \
+			 amount of seconds or a `{secs, nanos}` pair",
+		);
+	}
+
+	#[test]
+	fn deserialize_secs_nanos() {
+		let value = DurationFlex::try_from("1m30s250ms").unwrap();
+
+		assert_de_tokens(
+			&value.readable(),
+			&[
+				Token::Struct { name: "Duration", len: 2 },
+				Token::Str("secs"),
+				Token::U64(90),
+				Token::Str("nanos"),
+				Token::U32(250_000_000),
+				Token::StructEnd,
+			],
+		);
+
+		assert_de_tokens(
+			&value.readable(),
+			&[
+				Token::Map { len: Some(2) },
+				Token::Str("nanos"),
+				Token::I64(250_000_000),
+				Token::Str("secs"),
+				Token::I64(90),
+				Token::MapEnd,
+			],
+		);
+
+		assert_de_tokens(
+			&value.readable(),
+			&[Token::Seq { len: Some(2) }, Token::U64(90), Token::U32(250_000_000), Token::SeqEnd],
+		);
+		assert_de_tokens(
+			&value.readable(),
+			&[Token::Tuple { len: 2 }, Token::I64(89), Token::I64(1_250_000_000), Token::TupleEnd],
+		);
+
+		let value = DurationFlex::try_from("-1s500ms").unwrap();
+		assert_de_tokens(
+			&value.readable(),
+			&[Token::Seq { len: Some(2) }, Token::I64(-1), Token::I64(-500_000_000), Token::SeqEnd],
+		);
+		assert_de_tokens(
+			&value.readable(),
+			&[Token::Seq { len: Some(2) }, Token::I64(-2), Token::I64(500_000_000), Token::SeqEnd],
+		);
+
+		assert_de_tokens_error::<Readable<DurationFlex>>(
+			&[Token::Map { len: Some(1) }, Token::Str("secs"), Token::I64(90), Token::MapEnd],
+			"missing field `nanos`",
+		);
+
+		assert_de_tokens_error::<Readable<DurationFlex>>(
+			&[Token::Map { len: Some(1) }, Token::Str("millis")],
+			"unknown field `millis`, expected `secs` or `nanos`",
+		);
+	}
+
+	#[test]
+	fn deserialize_suggestion() {
+		assert_de_tokens_error::<Readable<DurationFlex>>(
+			&[Token::Str("5min")],
+			"invalid value: string \"5min\", did you mean `5m`?",
+		);
+
+		assert_de_tokens_error::<Readable<DurationFlex>>(
+			&[Token::Str("5y")],
+			"invalid value: string \"5y\", expected a String with the format weeks (w), days (d), hours (h), minutes \
+			 (m), seconds (s), milliseconds (ms), microseconds (us) and/or nanoseconds (ns), in order, an amount of \
+			 seconds or a `{secs, nanos}` pair",
+		);
+	}
+
+	#[cfg(feature = "clap")]
+	#[test]
+	fn clap() {
+		use clap::Parser;
+
+		#[derive(Parser)]
+		struct Arguments {
+			#[arg(long)]
+			duration: DurationFlex,
+		}
+
+		let arguments = Arguments::try_parse_from(["test", "--duration", "1h30m"]).unwrap();
+		assert_eq!(arguments.duration, DurationFlex::try_from("1h30m").unwrap());
+
+		let error = Arguments::try_parse_from(["test", "--duration", "5min"]).err().unwrap().to_string();
+		assert!(error.contains("unknown unit `min` at offset 1, did you mean `5m`?"), "{}", error);
+	}
+
 	#[cfg(feature = "validator")]
 	#[test]
 	fn validator() {
@@ -0,0 +1,386 @@
+use crate::unit::Unit;
+
+/// Which form of a unit name is used for an amount, as an index into the forms of [`Locale::names`], see
+/// [`Locale::plural`].
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub enum PluralRule {
+	/// Singular only for 1, like in English, German or Spanish, e.g. `0 hours`, `1 hour`, `2 hours`.
+	One,
+	/// Singular for 0 and 1, like in French, e.g. `0 heure`, `1 heure`, `2 heures`.
+	ZeroOne,
+	/// No plural, like in Japanese.
+	Invariant,
+	/// Any other rule, returning the index of the form for an amount. For instance, Polish has three forms: for 1,
+	/// for amounts ending in 2 to 4 (but not 12 to 14), and for the others, e.g. `1 godzina`, `2 godziny`,
+	/// `5 godzin`.
+	Custom(fn(u128) -> usize),
+}
+
+impl PluralRule {
+	/// Index of the form used for `amount`, e.g. `0` (singular) or `1` (plural) with [`PluralRule::One`].
+	pub fn form(self, amount: u128) -> usize {
+		match self {
+			PluralRule::One => (amount != 1) as usize,
+			PluralRule::ZeroOne => (amount > 1) as usize,
+			PluralRule::Invariant => 0,
+			PluralRule::Custom(rule) => rule(amount),
+		}
+	}
+}
+
+/// Translation table, with unit names, plural rule and how lists are written. Used to format with
+/// [`Humanize::locale`](crate::Humanize::locale) and to parse with
+/// [`ParseOptions::locale`](crate::ParseOptions::locale).
+///
+/// English is always available, while Portuguese, Spanish, German and Japanese are bundled with the `i18n` feature.
+/// Custom locales are based on one of them, replacing what differs:
+/// ```
+/// use duration_flex::{DurationFlex, Locale};
+///
+/// # pub fn main() {
+/// static TERSE: Locale = Locale::EN.separator(" ").conjunction(None);
+///
+/// let value = DurationFlex::try_from("1h30m").unwrap();
+/// assert_eq!(value.humanize().locale(&TERSE).to_string(), "1 hour 30 minutes");
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct Locale {
+	/// Names of each unit, from weeks down to nano-seconds, in each form of the [plural rule](Locale::plural), e.g.
+	/// `&["hour", "hours"]`. The last form is used for indexes past the end.
+	pub names: [&'static [&'static str]; 8],
+	/// Abbreviated names, in the same order and forms, e.g. `&["hr", "hrs"]`.
+	pub short_names: [&'static [&'static str]; 8],
+	/// Which form of the names is used for an amount.
+	pub plural: PluralRule,
+	/// Text between an amount and its unit name, usually a space.
+	pub spacing: &'static str,
+	/// Text between components, e.g. `, `.
+	pub separator: &'static str,
+	/// Word before the last component, instead of the separator, e.g. `and`.
+	pub conjunction: Option<&'static str>,
+}
+
+impl Locale {
+	/// German, e.g. `1 Stunde, 30 Minuten und 5 Sekunden`.
+	#[cfg(feature = "i18n")]
+	pub const DE: Locale = Locale {
+		names: [
+			&["Woche", "Wochen"],
+			&["Tag", "Tage"],
+			&["Stunde", "Stunden"],
+			&["Minute", "Minuten"],
+			&["Sekunde", "Sekunden"],
+			&["Millisekunde", "Millisekunden"],
+			&["Mikrosekunde", "Mikrosekunden"],
+			&["Nanosekunde", "Nanosekunden"],
+		],
+		short_names: [&["Wo"], &["Tg"], &["Std"], &["Min"], &["Sek"], &["ms"], &["µs"], &["ns"]],
+		plural: PluralRule::One,
+		spacing: " ",
+		separator: ", ",
+		conjunction: Some("und"),
+	};
+	/// English, e.g. `1 hour, 30 minutes and 5 seconds`.
+	pub const EN: Locale = Locale {
+		names: [
+			&["week", "weeks"],
+			&["day", "days"],
+			&["hour", "hours"],
+			&["minute", "minutes"],
+			&["second", "seconds"],
+			&["millisecond", "milliseconds"],
+			&["microsecond", "microseconds"],
+			&["nanosecond", "nanoseconds"],
+		],
+		short_names: [
+			&["wk", "wks"],
+			&["day", "days"],
+			&["hr", "hrs"],
+			&["min", "mins"],
+			&["sec", "secs"],
+			&["ms"],
+			&["us"],
+			&["ns"],
+		],
+		plural: PluralRule::One,
+		spacing: " ",
+		separator: ", ",
+		conjunction: Some("and"),
+	};
+	/// Spanish, e.g. `1 hora, 30 minutos y 5 segundos`.
+	#[cfg(feature = "i18n")]
+	pub const ES: Locale = Locale {
+		names: [
+			&["semana", "semanas"],
+			&["día", "días"],
+			&["hora", "horas"],
+			&["minuto", "minutos"],
+			&["segundo", "segundos"],
+			&["milisegundo", "milisegundos"],
+			&["microsegundo", "microsegundos"],
+			&["nanosegundo", "nanosegundos"],
+		],
+		short_names: [&["sem"], &["d"], &["h"], &["min"], &["s"], &["ms"], &["µs"], &["ns"]],
+		plural: PluralRule::One,
+		spacing: " ",
+		separator: ", ",
+		conjunction: Some("y"),
+	};
+	/// Japanese, e.g. `1時間30分5秒`.
+	#[cfg(feature = "i18n")]
+	pub const JA: Locale = Locale {
+		names: [&["週間"], &["日"], &["時間"], &["分"], &["秒"], &["ミリ秒"], &["マイクロ秒"], &["ナノ秒"]],
+		short_names: [&["週"], &["日"], &["時間"], &["分"], &["秒"], &["ミリ秒"], &["マイクロ秒"], &["ナノ秒"]],
+		plural: PluralRule::Invariant,
+		spacing: "",
+		separator: "",
+		conjunction: None,
+	};
+	/// Portuguese, e.g. `1 hora, 30 minutos e 5 segundos`.
+	#[cfg(feature = "i18n")]
+	pub const PT: Locale = Locale {
+		names: [
+			&["semana", "semanas"],
+			&["dia", "dias"],
+			&["hora", "horas"],
+			&["minuto", "minutos"],
+			&["segundo", "segundos"],
+			&["milissegundo", "milissegundos"],
+			&["microssegundo", "microssegundos"],
+			&["nanossegundo", "nanossegundos"],
+		],
+		short_names: [&["sem"], &["dia", "dias"], &["h"], &["min"], &["s"], &["ms"], &["µs"], &["ns"]],
+		plural: PluralRule::One,
+		spacing: " ",
+		separator: ", ",
+		conjunction: Some("e"),
+	};
+
+	/// Same locale, with other [names](Locale::names).
+	pub const fn names(mut self, value: [&'static [&'static str]; 8]) -> Self {
+		self.names = value;
+		self
+	}
+
+	/// Same locale, with other [abbreviated names](Locale::short_names).
+	pub const fn short_names(mut self, value: [&'static [&'static str]; 8]) -> Self {
+		self.short_names = value;
+		self
+	}
+
+	/// Same locale, with another [plural rule](Locale::plural).
+	pub const fn plural(mut self, value: PluralRule) -> Self {
+		self.plural = value;
+		self
+	}
+
+	/// Same locale, with another [spacing](Locale::spacing).
+	pub const fn spacing(mut self, value: &'static str) -> Self {
+		self.spacing = value;
+		self
+	}
+
+	/// Same locale, with another [separator](Locale::separator).
+	pub const fn separator(mut self, value: &'static str) -> Self {
+		self.separator = value;
+		self
+	}
+
+	/// Same locale, with another [conjunction](Locale::conjunction).
+	pub const fn conjunction(mut self, value: Option<&'static str>) -> Self {
+		self.conjunction = value;
+		self
+	}
+
+	/// Unit with the full or short `name`, in any form. With `case_insensitive`, `name` must be in lower case.
+	pub(crate) fn unit(&self, name: &str, case_insensitive: bool) -> Option<Unit> {
+		Unit::ALL.into_iter().find(|unit| {
+			let names = self.names[*unit as usize].iter().chain(self.short_names[*unit as usize]);
+
+			names.into_iter().any(
+				|candidate| {
+					if case_insensitive {
+						candidate.to_lowercase() == name
+					} else {
+						*candidate == name
+					}
+				},
+			)
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	#[cfg(feature = "i18n")]
+	use crate::Abbreviation;
+	use crate::{flex, DurationFlex, DurationFlexError, Grammar, Locale, PluralRule};
+
+	#[test]
+	fn plural() {
+		assert_eq!(PluralRule::One.form(0), 1);
+		assert_eq!(PluralRule::One.form(1), 0);
+		assert_eq!(PluralRule::One.form(2), 1);
+		assert_eq!(PluralRule::ZeroOne.form(0), 0);
+		assert_eq!(PluralRule::ZeroOne.form(1), 0);
+		assert_eq!(PluralRule::ZeroOne.form(2), 1);
+		assert_eq!(PluralRule::Invariant.form(2), 0);
+		assert_eq!(PluralRule::Custom(|amount| amount as usize % 3).form(5), 2);
+	}
+
+	#[test]
+	fn english() {
+		assert_eq!(flex("1h30m5s").humanize().locale(&Locale::EN).to_string(), "1 hour, 30 minutes and 5 seconds");
+		assert_eq!(
+			flex("1h30m5s").humanize().locale(&Locale::EN).conjunction(Some("&")).to_string(),
+			"1 hour, 30 minutes & 5 seconds"
+		);
+
+		let parser = DurationFlex::parser().allow_whitespace(true).locale(&Locale::EN);
+		assert_eq!(parser.parse("1 hour, 30 minutes and 5 seconds"), Ok(flex("1h30m5s")));
+		assert_eq!(parser.parse("2 wks and 1 day"), Ok(flex("2w1d")));
+		assert_eq!(
+			parser.parse("1 hour and"),
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 7,
+				text: "and".to_string(),
+				suggestion: None,
+				grammar: Grammar::Native
+			})
+		);
+		assert_eq!(
+			parser.parse("1 hour andy 5 seconds"),
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 7,
+				text: "andy 5 seconds".to_string(),
+				suggestion: None,
+				grammar: Grammar::Native
+			})
+		);
+		assert!(DurationFlex::parser().allow_whitespace(true).parse("1 hour and 5 seconds").is_err());
+	}
+
+	#[test]
+	fn custom() {
+		static FRENCH: Locale = Locale::EN
+			.names([
+				&["semaine", "semaines"],
+				&["jour", "jours"],
+				&["heure", "heures"],
+				&["minute", "minutes"],
+				&["seconde", "secondes"],
+				&["milliseconde", "millisecondes"],
+				&["microseconde", "microsecondes"],
+				&["nanoseconde", "nanosecondes"],
+			])
+			.plural(PluralRule::ZeroOne)
+			.conjunction(Some("et"));
+
+		assert_eq!(flex("0s").humanize().locale(&FRENCH).to_string(), "0 seconde");
+		assert_eq!(flex("2h1m").humanize().locale(&FRENCH).to_string(), "2 heures et 1 minute");
+
+		let parser = DurationFlex::parser().allow_whitespace(true).case_insensitive(true).locale(&FRENCH);
+		assert_eq!(parser.parse("2 Heures ET 1 minute"), Ok(flex("2h1m")));
+	}
+
+	#[test]
+	fn custom_plural() {
+		fn polish(amount: u128) -> usize {
+			match (amount % 10, amount % 100) {
+				_ if amount == 1 => 0,
+				(2..=4, 12..=14) => 2,
+				(2..=4, _) => 1,
+				_ => 2,
+			}
+		}
+
+		static POLISH: Locale = Locale::EN
+			.names([
+				&["tydzień", "tygodnie", "tygodni"],
+				&["dzień", "dni"],
+				&["godzina", "godziny", "godzin"],
+				&["minuta", "minuty", "minut"],
+				&["sekunda", "sekundy", "sekund"],
+				&["milisekunda", "milisekundy", "milisekund"],
+				&["mikrosekunda", "mikrosekundy", "mikrosekund"],
+				&["nanosekunda", "nanosekundy", "nanosekund"],
+			])
+			.plural(PluralRule::Custom(polish))
+			.conjunction(Some("i"));
+
+		assert_eq!(flex("1h").humanize().locale(&POLISH).to_string(), "1 godzina");
+		assert_eq!(flex("2h22m").humanize().locale(&POLISH).to_string(), "2 godziny i 22 minuty");
+		assert_eq!(flex("5h12m").humanize().locale(&POLISH).to_string(), "5 godzin i 12 minut");
+		assert_eq!(flex("5d").humanize().locale(&POLISH).to_string(), "5 dni");
+
+		let parser = DurationFlex::parser().allow_whitespace(true).locale(&POLISH);
+		assert_eq!(parser.parse("5 godzin i 12 minut"), Ok(flex("5h12m")));
+		assert_eq!(parser.parse("2 tygodnie, 1 dzień"), Ok(flex("2w1d")));
+	}
+
+	#[cfg(feature = "i18n")]
+	#[test]
+	fn bundled() {
+		let cases = [
+			(Locale::PT, "1 hora, 30 minutos e 1 segundo", "2 sem, 1 dia, 3 h e 500 ms"),
+			(Locale::ES, "1 hora, 30 minutos y 1 segundo", "2 sem, 1 d, 3 h y 500 ms"),
+			(Locale::DE, "1 Stunde, 30 Minuten und 1 Sekunde", "2 Wo, 1 Tg, 3 Std und 500 ms"),
+			(Locale::JA, "1時間30分1秒", "2週1日3時間500ミリ秒"),
+		];
+
+		for (locale, full, short) in cases {
+			let humanize = flex("1h30m1s").humanize().locale(&locale);
+			assert_eq!(humanize.to_string(), full);
+
+			let humanize = flex("2w1d3h500ms").humanize().locale(&locale).abbreviation(Abbreviation::Short);
+			assert_eq!(humanize.to_string(), short);
+		}
+	}
+
+	#[cfg(feature = "i18n")]
+	#[test]
+	fn parse() {
+		let pt = DurationFlex::parser().allow_whitespace(true).case_insensitive(true).locale(&Locale::PT);
+		assert_eq!(pt.parse("1 hora e 30 minutos"), Ok(flex("1h30m")));
+		assert_eq!(pt.parse("1h e 30min"), Ok(flex("1h30m")));
+		assert_eq!(pt.parse("2 Dias"), Ok(flex("2d")));
+
+		let es = DurationFlex::parser().allow_whitespace(true).locale(&Locale::ES);
+		assert_eq!(es.parse("1 día y 2 horas"), Ok(flex("1d2h")));
+
+		let de = DurationFlex::parser().allow_whitespace(true).case_insensitive(true).locale(&Locale::DE);
+		assert_eq!(de.parse("1 stunde und 30 minuten"), Ok(flex("1h30m")));
+
+		let ja = DurationFlex::parser().locale(&Locale::JA);
+		assert_eq!(ja.parse("1時間30分"), Ok(flex("1h30m")));
+		assert_eq!(ja.parse("1週間2日"), Ok(flex("1w2d")));
+
+		assert_eq!(
+			pt.parse("1 hora e 30 minuten"),
+			Err(DurationFlexError::UnknownUnit {
+				offset: 12,
+				unit: "minuten".to_string(),
+				suggestion: None,
+				grammar: Grammar::Native
+			})
+		);
+	}
+
+	#[cfg(feature = "i18n")]
+	#[test]
+	fn round_trip() {
+		for locale in [&Locale::EN, &Locale::PT, &Locale::ES, &Locale::DE, &Locale::JA] {
+			let parser = DurationFlex::parser().allow_whitespace(true).locale(locale);
+
+			for value in ["1w2d3h4m5s6ms7us8ns", "1h30m", "-2h1m", "0s"] {
+				for abbreviation in [Abbreviation::Full, Abbreviation::Short] {
+					let text = flex(value).humanize().locale(locale).abbreviation(abbreviation).to_string();
+					assert_eq!(parser.parse(&text), Ok(flex(value)), "{}", text);
+				}
+			}
+		}
+	}
+}
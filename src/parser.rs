@@ -0,0 +1,257 @@
+use crate::locale::Locale;
+use crate::unit::Unit;
+use crate::{DurationFlex, DurationFlexError, Grammar};
+
+/// Configurable parser, built with [`DurationFlex::parser`].
+///
+/// By default, it is as strict as [`DurationFlex::try_from`], only accepting the `1w2d3h4m5s6ms7us8ns` format. Each
+/// method relaxes one aspect of the grammar:
+/// ```
+/// use duration_flex::{DurationFlex, Unit};
+///
+/// # pub fn main() {
+/// let parser =
+/// 	DurationFlex::parser().allow_whitespace(true).long_units(true).default_unit(Unit::Second);
+///
+/// assert_eq!(parser.parse("1 hour 30 min").unwrap().to_string(), "1h30m");
+/// assert_eq!(parser.parse("30").unwrap().to_string(), "30s");
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ParseOptions {
+	whitespace: bool,
+	case_insensitive: bool,
+	any_order: bool,
+	sum_repeated: bool,
+	long_units: bool,
+	default_unit: Option<Unit>,
+	locale: Option<&'static Locale>,
+}
+
+impl ParseOptions {
+	/// Accepts everything [`ParseOptions::STRICT`] does, and a lot more.
+	pub(crate) const LENIENT: ParseOptions = ParseOptions {
+		whitespace: true,
+		case_insensitive: true,
+		any_order: true,
+		sum_repeated: true,
+		long_units: true,
+		default_unit: None,
+		locale: None,
+	};
+	/// The `1w2d3h4m5s6ms7us8ns` format, as accepted by [`DurationFlex::try_from`].
+	pub(crate) const STRICT: ParseOptions = ParseOptions {
+		whitespace: false,
+		case_insensitive: false,
+		any_order: false,
+		sum_repeated: false,
+		long_units: false,
+		default_unit: None,
+		locale: None,
+	};
+
+	/// Allow whitespace around the input, between components and between a number and its unit, e.g. `1h 30 m`.
+	pub fn allow_whitespace(mut self, value: bool) -> Self {
+		self.whitespace = value;
+		self
+	}
+
+	/// Match units ignoring case, e.g. `1H30M`.
+	pub fn case_insensitive(mut self, value: bool) -> Self {
+		self.case_insensitive = value;
+		self
+	}
+
+	/// Allow components in any order, e.g. `30m1h`.
+	pub fn any_order(mut self, value: bool) -> Self {
+		self.any_order = value;
+		self
+	}
+
+	/// Allow the same unit multiple times, summing them, e.g. `1h1h` is the same as `2h`.
+	pub fn sum_repeated(mut self, value: bool) -> Self {
+		self.sum_repeated = value;
+		self
+	}
+
+	/// Accept long unit names and common abbreviations, e.g. `1hour30mins`.
+	pub fn long_units(mut self, value: bool) -> Self {
+		self.long_units = value;
+		self
+	}
+
+	/// Unit of an input made of a number only, e.g. `30` is the same as `30s` with [`Unit::Second`].
+	pub fn default_unit(mut self, value: Unit) -> Self {
+		self.default_unit = Some(value);
+		self
+	}
+
+	/// Accept the unit names of `value`, with its separator and conjunction between components, e.g.
+	/// `1 hora e 30 minutos` with `Locale::PT`. Usually combined with [`ParseOptions::allow_whitespace`].
+	pub fn locale(mut self, value: &'static Locale) -> Self {
+		self.locale = Some(value);
+		self
+	}
+
+	/// Parses `input`, with the errors carrying suggestions just like [`DurationFlex::try_from`].
+	pub fn parse(&self, input: &str) -> Result<DurationFlex, DurationFlexError> {
+		parse(input, self).map_err(|error| error.with_suggestion(suggest(input)))
+	}
+}
+
+impl Default for ParseOptions {
+	fn default() -> Self {
+		ParseOptions::STRICT
+	}
+}
+
+/// Parses the `1w2d3h4m5s6ms7us8ns` format, relaxed by `options`.
+///
+/// The input is scanned component by component (digits followed by a unit), so errors can point at the exact
+/// component that caused them.
+fn parse(input: &str, options: &ParseOptions) -> Result<DurationFlex, DurationFlexError> {
+	let skip_whitespace = |offset: usize| {
+		if options.whitespace {
+			input.len() - input[offset..].trim_start().len()
+		} else {
+			offset
+		}
+	};
+
+	let mut offset = skip_whitespace(0);
+	let negative = match input.as_bytes().get(offset) {
+		Some(b'-') => {
+			offset = skip_whitespace(offset + 1);
+			true
+		},
+		Some(b'+') => {
+			offset = skip_whitespace(offset + 1);
+			false
+		},
+		_ => false,
+	};
+
+	if offset == input.len() {
+		return Err(DurationFlexError::EmptyInput { grammar: Grammar::Native });
+	}
+
+	let mut total = 0i128;
+	let mut previous: Option<Unit> = None;
+	let mut seen = [false; Unit::ALL.len()];
+
+	while offset < input.len() {
+		let digits_end = offset + input[offset..].find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len() - offset);
+		let unit_start = skip_whitespace(digits_end);
+		let unit_end =
+			unit_start + input[unit_start..].find(|c: char| !c.is_alphabetic()).unwrap_or(input.len() - unit_start);
+
+		let digits = &input[offset..digits_end];
+		let symbol = &input[unit_start..unit_end];
+
+		let unit = match options.default_unit {
+			Some(unit) if previous.is_none() && !digits.is_empty() && unit_start == input.len() => unit,
+			_ if digits.is_empty() || symbol.is_empty() => {
+				return Err(DurationFlexError::TrailingGarbage {
+					offset,
+					text: input[offset..].to_string(),
+					suggestion: None,
+					grammar: Grammar::Native,
+				});
+			},
+			_ => {
+				resolve_unit(symbol, options).ok_or_else(|| {
+					DurationFlexError::UnknownUnit {
+						offset: unit_start,
+						unit: symbol.to_string(),
+						suggestion: None,
+						grammar: Grammar::Native,
+					}
+				})?
+			},
+		};
+
+		if seen[unit as usize] && !options.sum_repeated {
+			return Err(DurationFlexError::DuplicatedUnit {
+				offset: unit_start,
+				unit: symbol.to_string(),
+				suggestion: None,
+			});
+		}
+
+		if previous.is_some_and(|previous| previous.nanos() < unit.nanos()) && !options.any_order {
+			return Err(DurationFlexError::UnitOutOfOrder {
+				offset: unit_start,
+				unit: symbol.to_string(),
+				suggestion: None,
+				grammar: Grammar::Native,
+			});
+		}
+
+		let overflow = || DurationFlexError::ComponentOverflow { offset, text: input[offset..unit_end].to_string() };
+		let value = digits.parse::<u64>().map_err(|_| overflow())?;
+		let component = value as i128 * unit.nanos() as i128;
+		let component = if negative { -component } else { component };
+		if DurationFlex::from_total_nanos(component).is_none() {
+			return Err(overflow());
+		}
+
+		total = total.checked_add(component).ok_or(DurationFlexError::OutOfRange)?;
+		previous = Some(unit);
+		seen[unit as usize] = true;
+		offset = skip_whitespace(unit_end);
+
+		if let (Some(locale), true) = (options.locale, offset < input.len()) {
+			let start = offset;
+			if let Some(end) = skip_word(input, offset, locale.separator.trim(), options.case_insensitive) {
+				offset = skip_whitespace(end);
+			}
+			if let Some(end) = skip_word(input, offset, locale.conjunction.unwrap_or(""), options.case_insensitive) {
+				offset = skip_whitespace(end);
+			}
+
+			if offset == input.len() {
+				return Err(DurationFlexError::TrailingGarbage {
+					offset: start,
+					text: input[start..].to_string(),
+					suggestion: None,
+					grammar: Grammar::Native,
+				});
+			}
+		}
+	}
+
+	DurationFlex::from_total_nanos(total).ok_or(DurationFlexError::OutOfRange)
+}
+
+/// Corrected, canonical, version of a rejected input, if it can be understood by [`ParseOptions::LENIENT`], e.g.
+/// `5min` becomes `5m` and `5s5d` becomes `5d5s`.
+fn suggest(input: &str) -> Option<String> {
+	parse(input, &ParseOptions::LENIENT).ok().map(|value| value.to_string())
+}
+
+fn resolve_unit(symbol: &str, options: &ParseOptions) -> Option<Unit> {
+	let lowercase;
+	let symbol = if options.case_insensitive {
+		lowercase = symbol.to_lowercase();
+		lowercase.as_str()
+	} else {
+		symbol
+	};
+
+	Unit::from_symbol(symbol)
+		.or_else(|| if options.long_units { Unit::from_name(symbol) } else { None })
+		.or_else(|| options.locale.and_then(|locale| locale.unit(symbol, options.case_insensitive)))
+}
+
+/// End of `word` if `input` has it at `offset`, not followed by another letter.
+fn skip_word(input: &str, offset: usize, word: &str, case_insensitive: bool) -> Option<usize> {
+	let end = offset + word.len();
+	let text = input.get(offset..end)?;
+	let matches = if case_insensitive { text.to_lowercase() == word.to_lowercase() } else { text == word };
+
+	if word.is_empty() || !matches || input[end..].starts_with(char::is_alphabetic) {
+		None
+	} else {
+		Some(end)
+	}
+}
@@ -0,0 +1,536 @@
+use std::fmt::{Display, Formatter};
+
+use crate::iso8601;
+use crate::unit::Unit;
+use crate::{DurationFlex, DurationFlexError, Grammar, NANOS_PER_SEC};
+
+/// Styles of PostgreSQL's `interval` text format, as selected by its `IntervalStyle` setting.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum IntervalStyle {
+	/// `postgres`, the default style, e.g. `1 day 02:03:04.5`.
+	#[default]
+	Postgres,
+	/// `postgres_verbose`, e.g. `@ 1 day 2 hours 3 mins 4.5 secs`, or `@ 1 hour ago` for negative intervals.
+	PostgresVerbose,
+	/// `sql_standard`, e.g. `1 2:03:04.5`, where a leading `-` applies to all the fields.
+	SqlStandard,
+	/// `iso_8601`, e.g. `P1DT2H3M4.5S`, where every field has its own sign.
+	Iso8601,
+}
+
+/// Formats a [`DurationFlex`] like a PostgreSQL `interval`, see [`DurationFlex::postgres`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Postgres {
+	value: DurationFlex,
+	style: IntervalStyle,
+}
+
+impl Postgres {
+	pub(crate) fn new(value: DurationFlex, style: IntervalStyle) -> Self {
+		Postgres { value, style }
+	}
+}
+
+impl Display for Postgres {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		format(&self.value, self.style, f)
+	}
+}
+
+/// Parses a PostgreSQL `interval`, as written by any of the [`IntervalStyle`]s, e.g. `1 day 02:03:04.5`,
+/// `@ 1 hour 30 mins ago`, `-1 2:03:04` or `P1DT-2H`.
+///
+/// `style` only changes how a leading `-` is understood: with [`IntervalStyle::SqlStandard`], it applies to all the
+/// fields (unless they have a sign of their own), otherwise only to the first one, just like PostgreSQL. Only the
+/// fixed-length units are supported, months, years (and larger) are rejected with
+/// [`DurationFlexError::VariableLengthUnit`].
+pub(crate) fn parse(input: &str, style: IntervalStyle) -> Result<DurationFlex, DurationFlexError> {
+	let start = input.len() - input.trim_start().len();
+	if input[start..].starts_with('P') {
+		return parse_iso8601(input, start + 1);
+	} else if start == input.len() {
+		return Err(DurationFlexError::EmptyInput { grammar: Grammar::Postgres });
+	}
+
+	let garbage = |offset: usize| {
+		DurationFlexError::TrailingGarbage {
+			offset,
+			text: input[offset..].to_string(),
+			suggestion: None,
+			grammar: Grammar::Postgres,
+		}
+	};
+	let skip_whitespace = |offset: usize| input.len() - input[offset..].trim_start().len();
+
+	// Components, in nano-seconds, and their explicit sign (`true` if negative), if any.
+	let mut components: Vec<(i128, Option<bool>)> = Vec::new();
+	let mut ago = false;
+	let mut offset = skip_whitespace(start);
+	if input[offset..].starts_with('@') {
+		offset = skip_whitespace(offset + 1);
+	}
+
+	while offset < input.len() {
+		if ago {
+			return Err(garbage(offset));
+		}
+
+		let word_end = offset + input[offset..].find(|c: char| !c.is_alphabetic()).unwrap_or(input.len() - offset);
+		if word_end > offset {
+			if !input[offset..word_end].eq_ignore_ascii_case("ago") || components.is_empty() {
+				return Err(garbage(offset));
+			}
+
+			ago = true;
+			offset = skip_whitespace(word_end);
+			continue;
+		}
+
+		let (number, number_end) = scan_number(input, offset).ok_or_else(|| garbage(offset))?;
+		let sign = match input.as_bytes()[offset] {
+			b'-' => Some(true),
+			b'+' => Some(false),
+			_ => None,
+		};
+
+		if input.as_bytes().get(number_end) == Some(&b':') {
+			let (component, end) = parse_time(input, offset)?;
+			components.push((component, sign));
+			offset = skip_whitespace(end);
+			continue;
+		}
+
+		let unit_start = skip_whitespace(number_end);
+		let unit_end =
+			unit_start + input[unit_start..].find(|c: char| !c.is_alphabetic()).unwrap_or(input.len() - unit_start);
+		let word = &input[unit_start..unit_end];
+
+		let (unit, end) = if !word.is_empty() && !word.eq_ignore_ascii_case("ago") {
+			(resolve_unit(word, unit_start)?, unit_end)
+		} else if starts_with_time(&input[unit_start..]) {
+			// A bare number followed by a time is in days, like in `1 02:03:04`.
+			(Unit::Day, number_end)
+		} else {
+			(Unit::Second, number_end)
+		};
+
+		let component = number
+			.value(unit)
+			.ok_or_else(|| DurationFlexError::ComponentOverflow { offset, text: input[offset..end].to_string() })?;
+		components.push((component, sign));
+		offset = skip_whitespace(end);
+	}
+
+	// With the SQL standard style, a leading `-` applies to all the fields, unless any other has its own sign.
+	let negate_all = style == IntervalStyle::SqlStandard
+		&& components.first().is_some_and(|(_, sign)| *sign == Some(true))
+		&& components.iter().skip(1).all(|(_, sign)| sign.is_none());
+
+	let mut total = 0i128;
+	for (index, (component, _)) in components.into_iter().enumerate() {
+		let component = if negate_all && index > 0 { -component } else { component };
+		total = total.checked_add(component).ok_or(DurationFlexError::OutOfRange)?;
+	}
+
+	DurationFlex::from_total_nanos(if ago { -total } else { total }).ok_or(DurationFlexError::OutOfRange)
+}
+
+/// Parses the ISO 8601 style, starting after the `P`, where every field may have its own sign and a fraction, e.g.
+/// `P1DT-2H-3.5M`.
+fn parse_iso8601(input: &str, mut offset: usize) -> Result<DurationFlex, DurationFlexError> {
+	let input = input.trim_end();
+	if offset == input.len() {
+		return Err(DurationFlexError::EmptyInput { grammar: Grammar::Iso8601 });
+	}
+
+	let mut total = 0i128;
+	let mut in_time = false;
+	while offset < input.len() {
+		if input.as_bytes()[offset] == b'T' && !in_time && offset + 1 < input.len() {
+			in_time = true;
+			offset += 1;
+			continue;
+		}
+
+		let component = iso8601::scan_component(input, offset, in_time, true)?;
+		total = total.checked_add(component.nanos).ok_or(DurationFlexError::OutOfRange)?;
+		offset = component.end;
+	}
+
+	DurationFlex::from_total_nanos(total).ok_or(DurationFlexError::OutOfRange)
+}
+
+/// Parses a time field, like `02:03:04.5`, `-2:03` (hours and minutes) or `3:04.5` (minutes and seconds, as it has a
+/// fraction), returning it in nano-seconds with where it ends.
+fn parse_time(input: &str, offset: usize) -> Result<(i128, usize), DurationFlexError> {
+	let end = offset + input[offset..].find(char::is_whitespace).unwrap_or(input.len() - offset);
+	let text = &input[offset..end];
+	let invalid = || {
+		DurationFlexError::TrailingGarbage {
+			offset,
+			text: input[offset..].to_string(),
+			suggestion: None,
+			grammar: Grammar::Postgres,
+		}
+	};
+
+	let (negative, unsigned) = match text.as_bytes()[0] {
+		b'-' => (true, &text[1..]),
+		b'+' => (false, &text[1..]),
+		_ => (false, text),
+	};
+
+	let fields: Vec<&str> = unsigned.split(':').collect();
+	let is_integer = |field: &str| !field.is_empty() && field.bytes().all(|c| c.is_ascii_digit());
+	let (hours, minutes, seconds) = match fields.as_slice() {
+		[minutes, seconds] if seconds.contains('.') => ("0", *minutes, *seconds),
+		[hours, minutes] => (*hours, *minutes, "0"),
+		[hours, minutes, seconds] => (*hours, *minutes, *seconds),
+		_ => return Err(invalid()),
+	};
+
+	let (whole_seconds, fraction) = seconds.split_once('.').unwrap_or((seconds, ""));
+	if !is_integer(hours)
+		|| !is_integer(minutes)
+		|| !is_integer(whole_seconds)
+		|| !fraction.bytes().all(|c| c.is_ascii_digit())
+	{
+		return Err(invalid());
+	}
+
+	let overflow = || DurationFlexError::ComponentOverflow { offset, text: text.to_string() };
+	let hours = hours.parse::<u64>().map_err(|_| overflow())?;
+	let minutes = minutes.parse::<u64>().map_err(|_| overflow())?;
+	let whole_seconds = whole_seconds.parse::<u64>().map_err(|_| overflow())?;
+	if minutes >= 60 || whole_seconds >= 60 {
+		return Err(overflow());
+	}
+
+	let fraction = &fraction[..fraction.len().min(9)];
+	let nanos = match fraction {
+		"" => 0,
+		_ => fraction.parse::<i128>().map_err(|_| overflow())? * 10i128.pow(9 - fraction.len() as u32),
+	};
+
+	let total = hours as i128 * Unit::Hour.nanos() as i128
+		+ minutes as i128 * Unit::Minute.nanos() as i128
+		+ whole_seconds as i128 * NANOS_PER_SEC as i128
+		+ nanos;
+	if DurationFlex::from_total_nanos(total).is_none() {
+		return Err(overflow());
+	}
+
+	Ok((if negative { -total } else { total }, end))
+}
+
+/// Whether `text` starts with a time field, like `02:03:04`.
+fn starts_with_time(text: &str) -> bool {
+	let unsigned = text.strip_prefix(['+', '-']).unwrap_or(text);
+	let digits = unsigned.find(|c: char| !c.is_ascii_digit()).unwrap_or(unsigned.len());
+
+	digits > 0 && unsigned[digits..].starts_with(':')
+}
+
+/// A signed decimal number, like `-1.5`.
+struct Number<'a> {
+	negative: bool,
+	integer: &'a str,
+	fraction: &'a str,
+}
+
+impl Number<'_> {
+	/// Amount of nano-seconds of this number of `unit`s, or `None` if it doesn't fit.
+	fn value(&self, unit: Unit) -> Option<i128> {
+		let value = iso8601::amount_nanos(self.integer, self.fraction, unit)?;
+		Some(if self.negative { -value } else { value })
+	}
+}
+
+/// Scans a signed decimal number at `offset`, returning it with where it ends, or `None` if there are no digits.
+fn scan_number(input: &str, offset: usize) -> Option<(Number<'_>, usize)> {
+	let digits_end =
+		|start: usize| start + input[start..].find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len() - start);
+
+	let (negative, start) = match input.as_bytes().get(offset) {
+		Some(b'-') => (true, offset + 1),
+		Some(b'+') => (false, offset + 1),
+		_ => (false, offset),
+	};
+
+	let integer_end = digits_end(start);
+	let (fraction, end) = match input.as_bytes().get(integer_end) {
+		Some(b'.') => {
+			let end = digits_end(integer_end + 1);
+			(&input[integer_end + 1..end], end)
+		},
+		_ => ("", integer_end),
+	};
+
+	let integer = &input[start..integer_end];
+	if integer.is_empty() && fraction.is_empty() {
+		return None;
+	}
+
+	Some((Number { negative, integer, fraction }, end))
+}
+
+/// Resolves a unit name, in any case, as accepted by PostgreSQL.
+fn resolve_unit(word: &str, offset: usize) -> Result<Unit, DurationFlexError> {
+	match word.to_lowercase().as_str() {
+		"w" | "week" | "weeks" => Ok(Unit::Week),
+		"d" | "day" | "days" => Ok(Unit::Day),
+		"h" | "hr" | "hrs" | "hour" | "hours" => Ok(Unit::Hour),
+		"m" | "min" | "mins" | "minute" | "minutes" => Ok(Unit::Minute),
+		"s" | "sec" | "secs" | "second" | "seconds" => Ok(Unit::Second),
+		"ms" | "msec" | "msecs" | "millisecon" | "millisecond" | "milliseconds" => Ok(Unit::Millisecond),
+		"us" | "usec" | "usecs" | "microsecon" | "microsecond" | "microseconds" => Ok(Unit::Microsecond),
+		"mon" | "mons" | "month" | "months" | "y" | "yr" | "yrs" | "year" | "years" | "dec" | "decs" | "decade"
+		| "decades" | "c" | "cent" | "century" | "centuries" | "mil" | "mils" | "millennium" | "millennia"
+		| "millenniums" => Err(DurationFlexError::VariableLengthUnit { offset, unit: word.to_string() }),
+		_ => {
+			Err(DurationFlexError::UnknownUnit {
+				offset,
+				unit: word.to_string(),
+				suggestion: None,
+				grammar: Grammar::Postgres,
+			})
+		},
+	}
+}
+
+/// Formats `value` like PostgreSQL's `interval` output in the given `style`. Days are always 24 hours long, and are the
+/// largest unit.
+///
+/// Unlike PostgreSQL, which is limited to micro-seconds, the fraction of seconds can have up to 9 digits.
+fn format(value: &DurationFlex, style: IntervalStyle, f: &mut Formatter<'_>) -> std::fmt::Result {
+	let total = value.total_nanos();
+	let sign = if total < 0 { "-" } else { "" };
+
+	let magnitude = total.unsigned_abs();
+	let days = magnitude / Unit::Day.nanos() as u128;
+	let hours = magnitude % Unit::Day.nanos() as u128 / Unit::Hour.nanos() as u128;
+	let minutes = magnitude % Unit::Hour.nanos() as u128 / Unit::Minute.nanos() as u128;
+	let secs = magnitude % Unit::Minute.nanos() as u128 / NANOS_PER_SEC as u128;
+	let nanos = magnitude % NANOS_PER_SEC as u128;
+	let has_time = !magnitude.is_multiple_of(Unit::Day.nanos() as u128);
+
+	match style {
+		IntervalStyle::Postgres => {
+			if days > 0 {
+				write!(f, "{}{} day{}", sign, days, if days == 1 && total > 0 { "" } else { "s" })?;
+			}
+
+			if days == 0 || has_time {
+				let separator = if days > 0 { " " } else { "" };
+				write!(f, "{}{}{:02}:{:02}:", separator, if has_time { sign } else { "" }, hours, minutes)?;
+				write_seconds(secs, nanos, true, f)?;
+			}
+		},
+		IntervalStyle::PostgresVerbose => {
+			f.write_str("@")?;
+			for (amount, unit) in [(days, "day"), (hours, "hour"), (minutes, "min")] {
+				if amount > 0 {
+					write!(f, " {} {}{}", amount, unit, if amount == 1 { "" } else { "s" })?;
+				}
+			}
+
+			if secs > 0 || nanos > 0 {
+				f.write_str(" ")?;
+				write_seconds(secs, nanos, false, f)?;
+				write!(f, " sec{}", if secs == 1 && nanos == 0 { "" } else { "s" })?;
+			}
+
+			if total == 0 {
+				f.write_str(" 0")?;
+			} else if total < 0 {
+				f.write_str(" ago")?;
+			}
+		},
+		IntervalStyle::SqlStandard => {
+			if total == 0 {
+				return f.write_str("0");
+			}
+
+			f.write_str(sign)?;
+			if days > 0 {
+				write!(f, "{} ", days)?;
+			}
+
+			write!(f, "{}:{:02}:", hours, minutes)?;
+			write_seconds(secs, nanos, true, f)?;
+		},
+		IntervalStyle::Iso8601 => {
+			if total == 0 {
+				return f.write_str("PT0S");
+			}
+
+			f.write_str("P")?;
+			if days > 0 {
+				write!(f, "{}{}D", sign, days)?;
+			}
+
+			if has_time {
+				f.write_str("T")?;
+			}
+			if hours > 0 {
+				write!(f, "{}{}H", sign, hours)?;
+			}
+			if minutes > 0 {
+				write!(f, "{}{}M", sign, minutes)?;
+			}
+			if secs > 0 || nanos > 0 {
+				f.write_str(sign)?;
+				write_seconds(secs, nanos, false, f)?;
+				f.write_str("S")?;
+			}
+		},
+	}
+
+	Ok(())
+}
+
+/// Writes seconds, optionally padded to 2 digits, and their fraction without trailing zeros, e.g. `04.5`.
+fn write_seconds(secs: u128, nanos: u128, padded: bool, f: &mut Formatter<'_>) -> std::fmt::Result {
+	if padded {
+		write!(f, "{:02}", secs)?;
+	} else {
+		write!(f, "{}", secs)?;
+	}
+
+	if nanos > 0 {
+		let fraction = format!("{:09}", nanos);
+		write!(f, ".{}", fraction.trim_end_matches('0'))?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{flex, DurationFlex, DurationFlexError, Grammar, IntervalStyle};
+
+	#[test]
+	fn format() {
+		let cases = [
+			// value, postgres, postgres_verbose, sql_standard, iso_8601
+			("0s", "00:00:00", "@ 0", "0", "PT0S"),
+			("1d2h3m4s500ms", "1 day 02:03:04.5", "@ 1 day 2 hours 3 mins 4.5 secs", "1 2:03:04.5", "P1DT2H3M4.5S"),
+			("1h30m", "01:30:00", "@ 1 hour 30 mins", "1:30:00", "PT1H30M"),
+			("2d", "2 days", "@ 2 days", "2 0:00:00", "P2D"),
+			("1s", "00:00:01", "@ 1 sec", "0:00:01", "PT1S"),
+			("1w1us", "7 days 00:00:00.000001", "@ 7 days 0.000001 secs", "7 0:00:00.000001", "P7DT0.000001S"),
+			("-1d2h", "-1 days -02:00:00", "@ 1 day 2 hours ago", "-1 2:00:00", "P-1DT-2H"),
+			("-1d", "-1 days", "@ 1 day ago", "-1 0:00:00", "P-1D"),
+			("-1m30s", "-00:01:30", "@ 1 min 30 secs ago", "-0:01:30", "PT-1M-30S"),
+		];
+
+		for (value, postgres, verbose, sql, iso) in cases {
+			let value = flex(value);
+			assert_eq!(value.postgres(IntervalStyle::Postgres).to_string(), postgres);
+			assert_eq!(value.postgres(IntervalStyle::PostgresVerbose).to_string(), verbose);
+			assert_eq!(value.postgres(IntervalStyle::SqlStandard).to_string(), sql);
+			assert_eq!(value.postgres(IntervalStyle::Iso8601).to_string(), iso);
+
+			for (style, text) in [
+				(IntervalStyle::Postgres, postgres),
+				(IntervalStyle::PostgresVerbose, verbose),
+				(IntervalStyle::SqlStandard, sql),
+				(IntervalStyle::Iso8601, iso),
+			] {
+				assert_eq!(DurationFlex::parse_postgres(text, style), Ok(value), "{}", text);
+			}
+		}
+	}
+
+	#[test]
+	fn parse() {
+		let parse = |input: &str| DurationFlex::parse_postgres(input, IntervalStyle::Postgres);
+
+		assert_eq!(parse("1 day 02:03:04.5"), Ok(flex("1d2h3m4s500ms")));
+		assert_eq!(parse("@ 1 hour 30 mins"), Ok(flex("1h30m")));
+		assert_eq!(parse("2 hours 30 minutes"), Ok(flex("2h30m")));
+		assert_eq!(parse("1day 2h"), Ok(flex("1d2h")));
+		assert_eq!(parse("1.5 days"), Ok(flex("1d12h")));
+		assert_eq!(parse("2 weeks"), Ok(flex("14d")));
+		assert_eq!(parse("5"), Ok(flex("5s")));
+		assert_eq!(parse("5 ago"), Ok(flex("-5s")));
+		assert_eq!(parse("1:30"), Ok(flex("1h30m")));
+		assert_eq!(parse("1:30.5"), Ok(flex("1m30s500ms")));
+		assert_eq!(parse("26:00:00"), Ok(flex("1d2h")));
+		assert_eq!(parse("1 DAY 3 MSEC 4 USECS"), Ok(flex("1d3ms4us")));
+		assert_eq!(parse("-1 days +02:00:00"), Ok(flex("-22h")));
+		assert_eq!(parse("PT1H30M"), Ok(flex("1h30m")));
+		assert_eq!(parse("P1.5D"), Ok(flex("1d12h")));
+		assert_eq!(parse("P1DT-2H"), Ok(flex("22h")));
+		assert_eq!(parse("PT-1,5M"), Ok(flex("-1m30s")));
+
+		// The leading sign only applies to all the fields with the SQL standard style.
+		assert_eq!(parse("-1 2:00:00"), Ok(flex("-22h")));
+		assert_eq!(DurationFlex::parse_postgres("-1 2:00:00", IntervalStyle::SqlStandard), Ok(flex("-1d2h")));
+		assert_eq!(DurationFlex::parse_postgres("-1 +2:00:00", IntervalStyle::SqlStandard), Ok(flex("-22h")));
+	}
+
+	#[test]
+	fn parse_errors() {
+		let parse = |input: &str| DurationFlex::parse_postgres(input, IntervalStyle::Postgres);
+
+		assert_eq!(parse(""), Err(DurationFlexError::EmptyInput { grammar: Grammar::Postgres }));
+		assert_eq!(parse("P"), Err(DurationFlexError::EmptyInput { grammar: Grammar::Iso8601 }));
+		assert_eq!(
+			parse("1 month"),
+			Err(DurationFlexError::VariableLengthUnit { offset: 2, unit: "month".to_string() })
+		);
+		assert_eq!(parse("P1Y"), Err(DurationFlexError::VariableLengthUnit { offset: 2, unit: "Y".to_string() }));
+		assert_eq!(
+			parse("1 fortnight"),
+			Err(DurationFlexError::UnknownUnit {
+				offset: 2,
+				unit: "fortnight".to_string(),
+				suggestion: None,
+				grammar: Grammar::Postgres
+			})
+		);
+		assert_eq!(
+			parse("1 hour ago 2 mins"),
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 11,
+				text: "2 mins".to_string(),
+				suggestion: None,
+				grammar: Grammar::Postgres
+			})
+		);
+		assert_eq!(
+			parse("01:60:00"),
+			Err(DurationFlexError::ComponentOverflow { offset: 0, text: "01:60:00".to_string() })
+		);
+
+		assert!(parse("ago").is_err());
+		assert!(parse("1:2:3:4").is_err());
+		assert!(parse("1:x").is_err());
+		assert!(parse("hour").is_err());
+		assert!(parse(". days").is_err());
+		assert!(parse("PT").is_err());
+		assert!(parse("99999999999999999999 days").is_err());
+	}
+
+	#[test]
+	fn help() {
+		let help = |input: &str| DurationFlex::parse_postgres(input, IntervalStyle::Postgres).unwrap_err().help();
+
+		assert_eq!(
+			help("1 fortnight"),
+			Some(
+				"valid units are weeks, days, hours, minutes, seconds, milliseconds and microseconds, or their \
+				 abbreviations like `mins`"
+			)
+		);
+		assert_eq!(
+			help("1 day x"),
+			Some("each component is a number followed by a unit, like `1 day`, or a time, like `02:30:00`")
+		);
+		assert_eq!(
+			help("P1X"),
+			Some("date designators are Y, M, W and D, and time designators, after `T`, are H, M and S")
+		);
+	}
+}
@@ -0,0 +1,241 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{DurationFlex, DurationFlexError, Grammar, NANOS_PER_MILLI};
+
+/// Largest Prometheus duration, in nano-seconds.
+const LIMIT: u64 = i64::MAX as u64;
+
+/// Units, from the largest to the smallest, with their size in milli-seconds. Years are always 365 days.
+const UNITS: [(&str, u64); 7] = [
+	("y", 365 * 24 * 60 * 60 * 1000),
+	("w", 7 * 24 * 60 * 60 * 1000),
+	("d", 24 * 60 * 60 * 1000),
+	("h", 60 * 60 * 1000),
+	("m", 60 * 1000),
+	("s", 1000),
+	("ms", 1),
+];
+
+/// Formats a [`DurationFlex`] like Prometheus' `model.Duration.String()`, see [`DurationFlex::prometheus`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Prometheus(pub(crate) DurationFlex);
+
+impl Display for Prometheus {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		format(&self.0, f)
+	}
+}
+
+/// Parses the Prometheus duration format, exactly like Prometheus' `model.ParseDuration`, e.g. `5m`, `1h30m` or `1y`.
+///
+/// Components must be in order, from years (`y`) down to milli-seconds (`ms`), and can't have a fraction. `0` is
+/// accepted without unit. Just like in Prometheus, the result must fit in an `i64` amount of nano-seconds.
+pub(crate) fn parse(input: &str) -> Result<DurationFlex, DurationFlexError> {
+	let garbage = |offset: usize| {
+		DurationFlexError::TrailingGarbage {
+			offset,
+			text: input[offset..].to_string(),
+			suggestion: None,
+			grammar: Grammar::Prometheus,
+		}
+	};
+
+	match input {
+		"0" => return Ok(DurationFlex::ZERO),
+		"" => return Err(DurationFlexError::EmptyInput { grammar: Grammar::Prometheus }),
+		_ => {},
+	}
+
+	let mut offset = 0;
+	let mut total = 0u64;
+	let mut previous: Option<usize> = None;
+	while offset < input.len() {
+		let digits_end = offset + input[offset..].find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len() - offset);
+		let unit_end =
+			digits_end + input[digits_end..].find(|c: char| c.is_ascii_digit()).unwrap_or(input.len() - digits_end);
+
+		let digits = &input[offset..digits_end];
+		let symbol = &input[digits_end..unit_end];
+		if digits.is_empty() || symbol.is_empty() {
+			return Err(garbage(offset));
+		}
+
+		let Some(position) = UNITS.iter().position(|(unit, _)| *unit == symbol) else {
+			return Err(DurationFlexError::UnknownUnit {
+				offset: digits_end,
+				unit: symbol.to_string(),
+				suggestion: None,
+				grammar: Grammar::Prometheus,
+			});
+		};
+
+		match previous {
+			Some(previous) if previous == position => {
+				return Err(DurationFlexError::DuplicatedUnit {
+					offset: digits_end,
+					unit: symbol.to_string(),
+					suggestion: None,
+				});
+			},
+			Some(previous) if previous > position => {
+				return Err(DurationFlexError::UnitOutOfOrder {
+					offset: digits_end,
+					unit: symbol.to_string(),
+					suggestion: None,
+					grammar: Grammar::Prometheus,
+				});
+			},
+			_ => {},
+		}
+
+		let size = UNITS[position].1 * NANOS_PER_MILLI;
+		let overflow = || DurationFlexError::ComponentOverflow { offset, text: input[offset..unit_end].to_string() };
+		let value = digits.parse::<u64>().map_err(|_| overflow())?;
+		if value > LIMIT / size {
+			return Err(overflow());
+		}
+
+		total = total.checked_add(value * size).filter(|total| *total <= LIMIT).ok_or(DurationFlexError::OutOfRange)?;
+		previous = Some(position);
+		offset = unit_end;
+	}
+
+	DurationFlex::from_total_nanos(total as i128).ok_or(DurationFlexError::OutOfRange)
+}
+
+/// Formats `value` like Prometheus' `model.Duration.String()`, truncated to milli-seconds. Years and weeks are only
+/// used when they are exact, e.g. `2w` but `15d`. Zero is `0s`.
+///
+/// Unlike Prometheus, negative durations are formatted too, prefixed by `-`.
+pub(crate) fn format(value: &DurationFlex, f: &mut Formatter<'_>) -> std::fmt::Result {
+	let mut millis = value.total_nanos().unsigned_abs() / NANOS_PER_MILLI as u128;
+	if millis == 0 {
+		return f.write_str("0s");
+	} else if value.total_nanos() < 0 {
+		f.write_str("-")?;
+	}
+
+	for (unit, size) in UNITS {
+		let size = size as u128;
+		if matches!(unit, "y" | "w") && !millis.is_multiple_of(size) {
+			continue;
+		}
+
+		let amount = millis / size;
+		if amount > 0 {
+			write!(f, "{}{}", amount, unit)?;
+			millis -= amount * size;
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{flex, DurationFlex, DurationFlexError, Grammar};
+
+	/// `TestParseDuration`, from Prometheus' `model/time_test.go`.
+	#[test]
+	fn parse() {
+		let cases = [
+			("0", "0s", "0s"),
+			("0w", "0s", "0s"),
+			("0s", "0s", "0s"),
+			("324ms", "324ms", "324ms"),
+			("3s", "3s", "3s"),
+			("5m", "5m", "5m"),
+			("1h", "1h", "1h"),
+			("4d", "4d", "4d"),
+			("4d1h", "4d1h", "4d1h"),
+			("14d", "2w", "2w"),
+			("3w", "3w", "3w"),
+			("3w2d1h", "3w2d1h", "23d1h"),
+			("10y", "3650d", "10y"),
+		];
+
+		for (input, expected, formatted) in cases {
+			let value = DurationFlex::parse_prometheus(input);
+			assert_eq!(value, Ok(flex(expected)), "{}", input);
+			assert_eq!(value.unwrap().prometheus().to_string(), formatted);
+		}
+
+		assert_eq!(DurationFlex::parse_prometheus("1h30m"), Ok(flex("1h30m")));
+		assert_eq!(DurationFlex::parse_prometheus("1y2w3d4h5m6s7ms"), Ok(flex("382d4h5m6s7ms")));
+		assert_eq!(DurationFlex::parse_prometheus("9223372036854ms"), Ok(flex("9223372036s854ms")));
+	}
+
+	#[test]
+	fn parse_errors() {
+		let cases = ["1", "1y1m1d", "-1w", "1.5d", "d", "294y", "200y10400w", "107675d", "2584200h", "1h1h", "1us"];
+
+		for input in cases {
+			assert!(DurationFlex::parse_prometheus(input).is_err(), "{}", input);
+		}
+
+		assert_eq!(
+			DurationFlex::parse_prometheus(""),
+			Err(DurationFlexError::EmptyInput { grammar: Grammar::Prometheus })
+		);
+		assert_eq!(
+			DurationFlex::parse_prometheus("1h1d"),
+			Err(DurationFlexError::UnitOutOfOrder {
+				offset: 3,
+				unit: "d".to_string(),
+				suggestion: None,
+				grammar: Grammar::Prometheus
+			})
+		);
+		assert_eq!(
+			DurationFlex::parse_prometheus("1h1h"),
+			Err(DurationFlexError::DuplicatedUnit { offset: 3, unit: "h".to_string(), suggestion: None })
+		);
+		assert_eq!(
+			DurationFlex::parse_prometheus("1us"),
+			Err(DurationFlexError::UnknownUnit {
+				offset: 1,
+				unit: "us".to_string(),
+				suggestion: None,
+				grammar: Grammar::Prometheus
+			})
+		);
+		assert_eq!(
+			DurationFlex::parse_prometheus("1h30"),
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 2,
+				text: "30".to_string(),
+				suggestion: None,
+				grammar: Grammar::Prometheus
+			})
+		);
+		assert_eq!(
+			DurationFlex::parse_prometheus("294y"),
+			Err(DurationFlexError::ComponentOverflow { offset: 0, text: "294y".to_string() })
+		);
+		assert_eq!(DurationFlex::parse_prometheus("200y10400w"), Err(DurationFlexError::OutOfRange));
+	}
+
+	#[test]
+	fn help() {
+		let help = |input: &str| DurationFlex::parse_prometheus(input).unwrap_err().help();
+
+		assert_eq!(help("1us"), Some("valid units are y, w, d, h, m, s and ms"));
+		assert_eq!(help("1h1y"), Some("units must be in the order y, w, d, h, m, s and ms, like `1h30m`"));
+		assert_eq!(
+			help("1h30"),
+			Some("each component is an integer followed by one of the units y, w, d, h, m, s or ms")
+		);
+	}
+
+	#[test]
+	fn format() {
+		assert_eq!(flex("0s").prometheus().to_string(), "0s");
+		assert_eq!(flex("999us").prometheus().to_string(), "0s");
+		assert_eq!(flex("1h30m").prometheus().to_string(), "1h30m");
+		assert_eq!(flex("1s500ms999us").prometheus().to_string(), "1s500ms");
+		assert_eq!(flex("15d").prometheus().to_string(), "15d");
+		assert_eq!(flex("366d").prometheus().to_string(), "366d");
+		assert_eq!(flex("730d").prometheus().to_string(), "2y");
+		assert_eq!(flex("-1h30m").prometheus().to_string(), "-1h30m");
+	}
+}
@@ -0,0 +1,169 @@
+use crate::unit::Unit;
+use crate::{DurationFlex, DurationFlexError};
+
+/// How [`DurationFlex::round_to_with`] picks a multiple of the quantum, e.g. rounding to minutes:
+///
+/// | Value    | `HalfUp` | `HalfEven` | `TowardZero` | `Floor` | `Ceil` |
+/// |----------|----------|------------|--------------|---------|--------|
+/// | `2m30s`  | `3m`     | `2m`       | `2m`         | `2m`    | `3m`   |
+/// | `3m30s`  | `4m`     | `4m`       | `3m`         | `3m`    | `4m`   |
+/// | `-2m30s` | `-3m`    | `-2m`      | `-2m`        | `-3m`   | `-2m`  |
+/// | `2m40s`  | `3m`     | `3m`       | `2m`         | `2m`    | `3m`   |
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum RoundingMode {
+	/// Nearest multiple, ties away from zero.
+	#[default]
+	HalfUp,
+	/// Nearest multiple, ties to the even multiple, avoiding a bias when rounding many values.
+	HalfEven,
+	/// Truncates, towards zero.
+	TowardZero,
+	/// Largest multiple that is not larger, towards negative infinity.
+	Floor,
+	/// Smallest multiple that is not smaller, towards positive infinity.
+	Ceil,
+}
+
+impl DurationFlex {
+	/// Rounds to the nearest multiple of `quantum`, ties away from zero:
+	/// ```
+	/// use duration_flex::{DurationFlex, Unit};
+	///
+	/// # pub fn main() {
+	/// let value = DurationFlex::try_from("1h12m30s").unwrap();
+	/// let quantum = DurationFlex::try_from("5m").unwrap();
+	/// assert_eq!(value.round_to(quantum).unwrap().to_string(), "1h15m");
+	/// assert_eq!(value.floor_to(quantum).unwrap().to_string(), "1h10m");
+	/// assert_eq!(value.trunc_to_unit(Unit::Hour).unwrap().to_string(), "1h");
+	/// # }
+	/// ```
+	///
+	/// Fails with [`DurationFlexError::InvalidQuantum`] if `quantum` is not positive, or with
+	/// [`DurationFlexError::OutOfRange`] if the result doesn't fit.
+	pub fn round_to(self, quantum: DurationFlex) -> Result<DurationFlex, DurationFlexError> {
+		self.round_to_with(quantum, RoundingMode::HalfUp)
+	}
+
+	/// Rounds to a multiple of `quantum`, as specified by `mode`, see [`RoundingMode`].
+	///
+	/// Fails with [`DurationFlexError::InvalidQuantum`] if `quantum` is not positive, or with
+	/// [`DurationFlexError::OutOfRange`] if the result doesn't fit.
+	pub fn round_to_with(self, quantum: DurationFlex, mode: RoundingMode) -> Result<DurationFlex, DurationFlexError> {
+		let quantum = quantum.total_nanos();
+		if quantum <= 0 {
+			return Err(DurationFlexError::InvalidQuantum);
+		}
+
+		let total = self.total_nanos();
+		let quotient = total / quantum;
+		let remainder = total % quantum;
+
+		let away = match mode {
+			RoundingMode::HalfUp => remainder.abs() * 2 >= quantum,
+			RoundingMode::HalfEven => {
+				remainder.abs() * 2 > quantum || remainder.abs() * 2 == quantum && quotient % 2 != 0
+			},
+			RoundingMode::TowardZero => false,
+			RoundingMode::Floor => remainder < 0,
+			RoundingMode::Ceil => remainder > 0,
+		};
+
+		let quotient = if away { quotient + remainder.signum() } else { quotient };
+		DurationFlex::from_total_nanos(quotient * quantum).ok_or(DurationFlexError::OutOfRange)
+	}
+
+	/// Rounds down to a multiple of `quantum`, towards negative infinity, see [`RoundingMode::Floor`].
+	pub fn floor_to(self, quantum: DurationFlex) -> Result<DurationFlex, DurationFlexError> {
+		self.round_to_with(quantum, RoundingMode::Floor)
+	}
+
+	/// Rounds up to a multiple of `quantum`, towards positive infinity, see [`RoundingMode::Ceil`].
+	pub fn ceil_to(self, quantum: DurationFlex) -> Result<DurationFlex, DurationFlexError> {
+		self.round_to_with(quantum, RoundingMode::Ceil)
+	}
+
+	/// Truncates to a multiple of `quantum`, towards zero, see [`RoundingMode::TowardZero`].
+	pub fn trunc_to(self, quantum: DurationFlex) -> Result<DurationFlex, DurationFlexError> {
+		self.round_to_with(quantum, RoundingMode::TowardZero)
+	}
+
+	/// Rounds to the nearest whole `unit`, ties away from zero, e.g. `2h` for `1h30m` and [`Unit::Hour`].
+	pub fn round_to_unit(self, unit: Unit) -> Result<DurationFlex, DurationFlexError> {
+		self.round_to(unit.duration())
+	}
+
+	/// Rounds down to a whole `unit`, towards negative infinity.
+	pub fn floor_to_unit(self, unit: Unit) -> Result<DurationFlex, DurationFlexError> {
+		self.floor_to(unit.duration())
+	}
+
+	/// Rounds up to a whole `unit`, towards positive infinity.
+	pub fn ceil_to_unit(self, unit: Unit) -> Result<DurationFlex, DurationFlexError> {
+		self.ceil_to(unit.duration())
+	}
+
+	/// Truncates to a whole `unit`, towards zero.
+	pub fn trunc_to_unit(self, unit: Unit) -> Result<DurationFlex, DurationFlexError> {
+		self.trunc_to(unit.duration())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{flex, DurationFlex, DurationFlexError, RoundingMode, Unit};
+
+	#[test]
+	fn modes() {
+		let cases = [
+			("2m30s", ["3m", "2m", "2m", "2m", "3m"]),
+			("3m30s", ["4m", "4m", "3m", "3m", "4m"]),
+			("-2m30s", ["-3m", "-2m", "-2m", "-3m", "-2m"]),
+			("-3m30s", ["-4m", "-4m", "-3m", "-4m", "-3m"]),
+			("2m40s", ["3m", "3m", "2m", "2m", "3m"]),
+			("-2m20s", ["-2m", "-2m", "-2m", "-3m", "-2m"]),
+			("2m", ["2m", "2m", "2m", "2m", "2m"]),
+			("-1ns", ["0s", "0s", "0s", "-1m", "0s"]),
+			("0s", ["0s", "0s", "0s", "0s", "0s"]),
+		];
+		let modes = [
+			RoundingMode::HalfUp,
+			RoundingMode::HalfEven,
+			RoundingMode::TowardZero,
+			RoundingMode::Floor,
+			RoundingMode::Ceil,
+		];
+
+		for (value, expected) in cases {
+			for (mode, expected) in modes.into_iter().zip(expected) {
+				assert_eq!(flex(value).round_to_with(flex("1m"), mode), Ok(flex(expected)), "{} {:?}", value, mode);
+			}
+		}
+	}
+
+	#[test]
+	fn quantum() {
+		assert_eq!(flex("1h12m30s").round_to(flex("5m")), Ok(flex("1h15m")));
+		assert_eq!(flex("1h12m29s").round_to(flex("5m")), Ok(flex("1h10m")));
+		assert_eq!(flex("1h12m30s").floor_to(flex("5m")), Ok(flex("1h10m")));
+		assert_eq!(flex("1h10m1ns").ceil_to(flex("5m")), Ok(flex("1h15m")));
+		assert_eq!(flex("-1h12m30s").trunc_to(flex("5m")), Ok(flex("-1h10m")));
+		assert_eq!(flex("1s500ms").round_to(flex("1s1ns")), Ok(flex("1s1ns")));
+		assert_eq!(flex("1h").round_to(flex("0s")), Err(DurationFlexError::InvalidQuantum));
+		assert_eq!(flex("1h").round_to(flex("-5m")), Err(DurationFlexError::InvalidQuantum));
+
+		assert_eq!(DurationFlex::MAX.ceil_to(flex("1h")), Err(DurationFlexError::OutOfRange));
+		assert_eq!(DurationFlex::MAX.floor_to(DurationFlex::MAX), Ok(DurationFlex::MAX));
+	}
+
+	#[test]
+	fn units() {
+		assert_eq!(flex("1h30m").round_to_unit(Unit::Hour), Ok(flex("2h")));
+		assert_eq!(flex("1h29m").round_to_unit(Unit::Hour), Ok(flex("1h")));
+		assert_eq!(flex("-1h30m").floor_to_unit(Unit::Hour), Ok(flex("-2h")));
+		assert_eq!(flex("1h1ns").ceil_to_unit(Unit::Hour), Ok(flex("2h")));
+		assert_eq!(flex("1d23h59m").trunc_to_unit(Unit::Day), Ok(flex("1d")));
+		assert_eq!(flex("1s999ms500us").round_to_unit(Unit::Millisecond), Ok(flex("2s")));
+		assert_eq!(flex("10d").round_to_unit(Unit::Week), Ok(flex("1w")));
+		assert_eq!(flex("1s1ns").trunc_to_unit(Unit::Nanosecond), Ok(flex("1s1ns")));
+	}
+}
@@ -0,0 +1,619 @@
+//! Alternative [`serde`] representations, to be used with `#[serde(with = "...")]`.
+//!
+//! - [`as_secs`]: integer amount of seconds, truncated towards zero.
+//! - [`as_millis`]: integer amount of milli-seconds, truncated towards zero.
+//! - [`as_secs_f64`]: fractional amount of seconds.
+//! - [`as_iso8601`]: ISO 8601 String, like `PT1H30M`.
+//! - [`as_std`]: same as [`std::time::Duration`], a `{secs, nanos}` struct. Can't represent negative durations.
+//! - [`default_secs`] and [`default_millis`]: serialize like [`DurationFlex`] itself, but also deserialize bare numbers
+//!   (integers, floats or strings) in the declared unit, which is useful when migrating configuration files that used
+//!   plain numbers.
+//!
+//! Each module has `option` and `vec` sub-modules, for `Option<DurationFlex>` and `Vec<DurationFlex>`:
+//! ```
+//! use duration_flex::DurationFlex;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//! 	#[serde(with = "duration_flex::serde::default_secs")]
+//! 	timeout: DurationFlex,
+//! 	#[serde(with = "duration_flex::serde::as_millis::option")]
+//! 	delay: Option<DurationFlex>,
+//! 	#[serde(with = "duration_flex::serde::as_iso8601::vec")]
+//! 	backoff: Vec<DurationFlex>,
+//! }
+//! ```
+
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+use std::time;
+
+use ::serde::de::{Error, MapAccess, SeqAccess, Unexpected, Visitor};
+use ::serde::ser::Error as _;
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{DurationFlex, DurationFlexVisitor, Unit, NANOS_PER_MILLI, NANOS_PER_SEC};
+
+/// A wire representation of [`DurationFlex`].
+trait Representation {
+	fn serialize<S>(value: &DurationFlex, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer;
+
+	fn deserialize<'de, D>(deserializer: D) -> Result<DurationFlex, D::Error>
+	where
+		D: Deserializer<'de>;
+}
+
+/// Adapter to serialize with a [`Representation`] inside containers.
+struct Ser<'a, R>(&'a DurationFlex, PhantomData<R>);
+
+impl<R: Representation> Serialize for Ser<'_, R> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		R::serialize(self.0, serializer)
+	}
+}
+
+/// Adapter to deserialize with a [`Representation`] inside containers.
+struct De<R>(DurationFlex, PhantomData<R>);
+
+impl<'de, R: Representation> Deserialize<'de> for De<R> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		R::deserialize(deserializer).map(|value| De(value, PhantomData))
+	}
+}
+
+macro_rules! representation {
+	($(#[$doc:meta])* $module:ident, $representation:ty) => {
+		$(#[$doc])*
+		pub mod $module {
+			use ::serde::{Deserializer, Serializer};
+
+			use crate::DurationFlex;
+
+			/// Serializes `value`, see the [module](self) documentation.
+			pub fn serialize<S>(value: &DurationFlex, serializer: S) -> Result<S::Ok, S::Error>
+			where
+				S: Serializer,
+			{
+				<$representation as crate::serde::Representation>::serialize(value, serializer)
+			}
+
+			/// Deserializes a value, see the [module](self) documentation.
+			pub fn deserialize<'de, D>(deserializer: D) -> Result<DurationFlex, D::Error>
+			where
+				D: Deserializer<'de>,
+			{
+				<$representation as crate::serde::Representation>::deserialize(deserializer)
+			}
+
+			/// Same as the parent module, for `Option<DurationFlex>`.
+			pub mod option {
+				use std::marker::PhantomData;
+
+				use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+				use crate::serde::{De, Ser};
+				use crate::DurationFlex;
+
+				/// Serializes `value`, see the parent module documentation.
+				pub fn serialize<S>(value: &Option<DurationFlex>, serializer: S) -> Result<S::Ok, S::Error>
+				where
+					S: Serializer,
+				{
+					value.as_ref().map(|value| Ser::<$representation>(value, PhantomData)).serialize(serializer)
+				}
+
+				/// Deserializes a value, see the parent module documentation.
+				pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DurationFlex>, D::Error>
+				where
+					D: Deserializer<'de>,
+				{
+					Option::<De<$representation>>::deserialize(deserializer).map(|value| value.map(|value| value.0))
+				}
+			}
+
+			/// Same as the parent module, for `Vec<DurationFlex>`.
+			pub mod vec {
+				use std::marker::PhantomData;
+
+				use ::serde::{Deserialize, Deserializer, Serializer};
+
+				use crate::serde::{De, Ser};
+				use crate::DurationFlex;
+
+				/// Serializes `values`, see the parent module documentation.
+				pub fn serialize<S>(values: &[DurationFlex], serializer: S) -> Result<S::Ok, S::Error>
+				where
+					S: Serializer,
+				{
+					serializer.collect_seq(values.iter().map(|value| Ser::<$representation>(value, PhantomData)))
+				}
+
+				/// Deserializes values, see the parent module documentation.
+				pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<DurationFlex>, D::Error>
+				where
+					D: Deserializer<'de>,
+				{
+					Vec::<De<$representation>>::deserialize(deserializer)
+						.map(|values| values.into_iter().map(|value| value.0).collect())
+				}
+			}
+		}
+	};
+}
+
+representation!(
+	/// Integer amount of seconds, truncated towards zero, e.g. `90` for `1m30s500ms`.
+	as_secs,
+	crate::serde::Secs
+);
+
+representation!(
+	/// Integer amount of milli-seconds, truncated towards zero, e.g. `90500` for `1m30s500ms`.
+	as_millis,
+	crate::serde::Millis
+);
+
+representation!(
+	/// Fractional amount of seconds, e.g. `90.5` for `1m30s500ms`. Deserialization rounds to the nearest nano-second.
+	as_secs_f64,
+	crate::serde::SecsF64
+);
+
+representation!(
+	/// ISO 8601 duration String, e.g. `PT1M30.5S` for `1m30s500ms`. Days are the largest unit used when serializing.
+	as_iso8601,
+	crate::serde::Iso8601
+);
+
+representation!(
+	/// Same as [`std::time::Duration`], a `{secs, nanos}` struct. Serializing a negative duration fails.
+	as_std,
+	crate::serde::Std
+);
+
+representation!(
+	/// Serializes like [`DurationFlex`], but bare numbers are seconds, e.g. `30` and `"30"` are the same as `"30s"`, and
+	/// `1.5` is the same as `"1s500ms"`.
+	default_secs,
+	crate::serde::DefaultUnit<crate::serde::Second>
+);
+
+representation!(
+	/// Serializes like [`DurationFlex`], but bare numbers are milli-seconds, e.g. `1500` and `"1500"` are the same as
+	/// `"1s500ms"`, and `1.5` is the same as `"1ms500us"`.
+	default_millis,
+	crate::serde::DefaultUnit<crate::serde::Millisecond>
+);
+
+struct Secs;
+
+impl Representation for Secs {
+	fn serialize<S>(value: &DurationFlex, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_i64(value.secs())
+	}
+
+	fn deserialize<'de, D>(deserializer: D) -> Result<DurationFlex, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		i64::deserialize(deserializer).map(|secs| DurationFlex { secs, nanos: 0 })
+	}
+}
+
+struct Millis;
+
+impl Representation for Millis {
+	fn serialize<S>(value: &DurationFlex, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let millis = value.total_nanos() / NANOS_PER_MILLI as i128;
+		let millis = i64::try_from(millis)
+			.map_err(|_| S::Error::custom(format_args!("duration `{}` is too large for milliseconds", value)))?;
+
+		serializer.serialize_i64(millis)
+	}
+
+	fn deserialize<'de, D>(deserializer: D) -> Result<DurationFlex, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		i64::deserialize(deserializer).map(|millis| {
+			DurationFlex { secs: millis / 1_000, nanos: (millis % 1_000 * NANOS_PER_MILLI as i64) as i32 }
+		})
+	}
+}
+
+struct SecsF64;
+
+impl Representation for SecsF64 {
+	fn serialize<S>(value: &DurationFlex, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_f64(value.secs() as f64 + value.nanos() as f64 / NANOS_PER_SEC as f64)
+	}
+
+	fn deserialize<'de, D>(deserializer: D) -> Result<DurationFlex, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let secs = f64::deserialize(deserializer)?;
+
+		DurationFlex::from_secs_f64_rounded(secs)
+			.ok_or_else(|| D::Error::invalid_value(Unexpected::Float(secs), &"a finite amount of seconds"))
+	}
+}
+
+struct Iso8601;
+
+impl Representation for Iso8601 {
+	fn serialize<S>(value: &DurationFlex, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.collect_str(&value.iso8601())
+	}
+
+	fn deserialize<'de, D>(deserializer: D) -> Result<DurationFlex, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct Iso8601Visitor;
+
+		impl Visitor<'_> for Iso8601Visitor {
+			type Value = DurationFlex;
+
+			fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+				formatter.write_str("an ISO 8601 duration String, like `PT1H30M`")
+			}
+
+			fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+			where
+				E: Error,
+			{
+				DurationFlex::parse_iso8601(v).map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+			}
+		}
+
+		deserializer.deserialize_str(Iso8601Visitor)
+	}
+}
+
+struct Std;
+
+impl Representation for Std {
+	fn serialize<S>(value: &DurationFlex, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		time::Duration::try_from(*value)
+			.map_err(|_| {
+				S::Error::custom(format_args!("negative duration `{}` can't be a std::time::Duration", value))
+			})?
+			.serialize(serializer)
+	}
+
+	fn deserialize<'de, D>(deserializer: D) -> Result<DurationFlex, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let value = time::Duration::deserialize(deserializer)?;
+
+		i64::try_from(value.as_secs())
+			.map(|secs| DurationFlex { secs, nanos: value.subsec_nanos() as i32 })
+			.map_err(|_| D::Error::custom(format_args!("duration of {} seconds is out of range", value.as_secs())))
+	}
+}
+
+/// Unit of the bare numbers accepted by [`DefaultUnit`].
+trait BareUnit {
+	const UNIT: Unit;
+}
+
+struct Second;
+
+impl BareUnit for Second {
+	const UNIT: Unit = Unit::Second;
+}
+
+struct Millisecond;
+
+impl BareUnit for Millisecond {
+	const UNIT: Unit = Unit::Millisecond;
+}
+
+struct DefaultUnit<U>(PhantomData<U>);
+
+impl<U: BareUnit> Representation for DefaultUnit<U> {
+	fn serialize<S>(value: &DurationFlex, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		value.serialize(serializer)
+	}
+
+	fn deserialize<'de, D>(deserializer: D) -> Result<DurationFlex, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		if deserializer.is_human_readable() {
+			deserializer.deserialize_any(DefaultUnitVisitor(U::UNIT))
+		} else {
+			DurationFlex::deserialize(deserializer)
+		}
+	}
+}
+
+struct DefaultUnitVisitor(Unit);
+
+impl<'de> Visitor<'de> for DefaultUnitVisitor {
+	type Value = DurationFlex;
+
+	fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+		write!(formatter, "a duration String, an amount of `{}` or a `{{secs, nanos}}` pair", self.0.symbol())
+	}
+
+	fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		DurationFlex::from_total_nanos(v as i128 * self.0.nanos() as i128)
+			.ok_or_else(|| Error::invalid_value(Unexpected::Signed(v), &self))
+	}
+
+	fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		DurationFlex::from_total_nanos(v as i128 * self.0.nanos() as i128)
+			.ok_or_else(|| Error::invalid_value(Unexpected::Unsigned(v), &self))
+	}
+
+	fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		DurationFlex::from_secs_f64_rounded(v * self.0.nanos() as f64 / NANOS_PER_SEC as f64)
+			.ok_or_else(|| Error::invalid_value(Unexpected::Float(v), &self))
+	}
+
+	fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		DurationFlex::parser().default_unit(self.0).parse(v).map_err(|error| {
+			match error.suggestion() {
+				Some(suggestion) => {
+					Error::custom(format_args!("invalid value: {}, did you mean `{}`?", Unexpected::Str(v), suggestion))
+				},
+				None => Error::invalid_value(Unexpected::Str(v), &self),
+			}
+		})
+	}
+
+	fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		DurationFlexVisitor.visit_seq(seq)
+	}
+
+	fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'de>,
+	{
+		DurationFlexVisitor.visit_map(map)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use ::serde::{Deserialize, Serialize};
+	use serde_test::{
+		assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_ser_tokens_error, assert_tokens, Configure,
+		Readable, Token,
+	};
+
+	use crate::{flex, DurationFlex};
+
+	#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+	struct Config {
+		#[serde(with = "crate::serde::default_secs")]
+		secs: DurationFlex,
+		#[serde(with = "crate::serde::default_millis")]
+		millis: DurationFlex,
+	}
+
+	fn tokens(secs: Token, millis: Token) -> [Token; 6] {
+		[
+			Token::Struct { name: "Config", len: 2 },
+			Token::Str("secs"),
+			secs,
+			Token::Str("millis"),
+			millis,
+			Token::StructEnd,
+		]
+	}
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	#[serde(transparent)]
+	struct AsSecs(#[serde(with = "crate::serde::as_secs")] DurationFlex);
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	#[serde(transparent)]
+	struct AsMillis(#[serde(with = "crate::serde::as_millis")] DurationFlex);
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	#[serde(transparent)]
+	struct AsSecsF64(#[serde(with = "crate::serde::as_secs_f64")] DurationFlex);
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	#[serde(transparent)]
+	struct AsIso8601(#[serde(with = "crate::serde::as_iso8601")] DurationFlex);
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	#[serde(transparent)]
+	struct AsStd(#[serde(with = "crate::serde::as_std")] DurationFlex);
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	#[serde(transparent)]
+	struct AsSecsOption(#[serde(with = "crate::serde::as_secs::option")] Option<DurationFlex>);
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	#[serde(transparent)]
+	struct AsIso8601Vec(#[serde(with = "crate::serde::as_iso8601::vec")] Vec<DurationFlex>);
+
+	#[test]
+	fn as_secs() {
+		assert_tokens(&AsSecs(flex("1m30s")), &[Token::I64(90)]);
+		assert_tokens(&AsSecs(flex("-1m30s")), &[Token::I64(-90)]);
+		assert_de_tokens(&AsSecs(flex("1m30s")), &[Token::U32(90)]);
+
+		assert_ser_tokens(&AsSecs(flex("1m30s999ms")), &[Token::I64(90)]);
+	}
+
+	#[test]
+	fn as_millis() {
+		assert_tokens(&AsMillis(flex("1m30s500ms")), &[Token::I64(90_500)]);
+		assert_tokens(&AsMillis(flex("-1s500ms")), &[Token::I64(-1_500)]);
+		assert_ser_tokens(&AsMillis(flex("1s500ms999us")), &[Token::I64(1_500)]);
+
+		assert_ser_tokens_error(
+			&AsMillis(flex("9223372036854775807s")),
+			&[],
+			"duration `15250284452471w3d15h30m7s` is too large for milliseconds",
+		);
+	}
+
+	#[test]
+	fn as_secs_f64() {
+		assert_tokens(&AsSecsF64(flex("1m30s500ms")), &[Token::F64(90.5)]);
+		assert_tokens(&AsSecsF64(flex("-250ms")), &[Token::F64(-0.25)]);
+		assert_de_tokens(&AsSecsF64(flex("2s")), &[Token::I64(2)]);
+
+		assert_de_tokens_error::<AsSecsF64>(
+			&[Token::F64(f64::INFINITY)],
+			"invalid value: floating point `inf`, expected a finite amount of seconds",
+		);
+	}
+
+	#[test]
+	fn as_iso8601() {
+		assert_tokens(&AsIso8601(flex("1d1m30s500ms")), &[Token::Str("P1DT1M30.5S")]);
+		assert_tokens(&AsIso8601(flex("-1h")), &[Token::Str("-PT1H")]);
+		assert_de_tokens(&AsIso8601(flex("1w")), &[Token::Str("P1W")]);
+
+		assert_de_tokens_error::<AsIso8601>(
+			&[Token::Str("1h")],
+			"invalid value: string \"1h\", expected an ISO 8601 duration String, like `PT1H30M`",
+		);
+	}
+
+	#[test]
+	fn as_std() {
+		let tokens = [
+			Token::Struct { name: "Duration", len: 2 },
+			Token::Str("secs"),
+			Token::U64(90),
+			Token::Str("nanos"),
+			Token::U32(500_000_000),
+			Token::StructEnd,
+		];
+
+		assert_tokens(&AsStd(flex("1m30s500ms")), &tokens);
+
+		assert_ser_tokens_error(&AsStd(flex("-1s")), &[], "negative duration `-1s` can't be a std::time::Duration");
+	}
+
+	#[test]
+	fn option_and_vec() {
+		assert_tokens(&AsSecsOption(Some(flex("1m"))), &[Token::Some, Token::I64(60)]);
+		assert_tokens(&AsSecsOption(None), &[Token::None]);
+
+		assert_tokens(
+			&AsIso8601Vec(vec![flex("1h"), flex("2m")]),
+			&[Token::Seq { len: Some(2) }, Token::Str("PT1H"), Token::Str("PT2M"), Token::SeqEnd],
+		);
+	}
+
+	#[test]
+	fn default_unit() {
+		let value = Config { secs: flex("30s"), millis: flex("1s500ms") };
+
+		assert_tokens(&value.readable(), &tokens(Token::Str("30s"), Token::Str("1s500ms")));
+		assert_de_tokens(&value.readable(), &tokens(Token::U64(30), Token::I64(1500)));
+		assert_de_tokens(&value.readable(), &tokens(Token::Str("30"), Token::Str("1500")));
+
+		let value = Config { secs: flex("-30s"), millis: flex("-1s500ms") };
+
+		assert_de_tokens(&value.readable(), &tokens(Token::I64(-30), Token::Str("-1500")));
+
+		let tuple = |secs, nanos| [Token::Tuple { len: 2 }, Token::I64(secs), Token::I32(nanos), Token::TupleEnd];
+		let tokens = [
+			&[Token::Struct { name: "Config", len: 2 }, Token::Str("secs")][..],
+			&tuple(-30, 0),
+			&[Token::Str("millis")],
+			&tuple(-1, -500_000_000),
+			&[Token::StructEnd],
+		]
+		.concat();
+
+		assert_tokens(&value.compact(), &tokens);
+	}
+
+	#[test]
+	fn default_unit_floats_and_pairs() {
+		let value = Config { secs: flex("1s500ms"), millis: flex("1ms500us") };
+
+		assert_de_tokens(&value.readable(), &tokens(Token::F64(1.5), Token::F64(1.5)));
+
+		let map = |secs, nanos| {
+			[
+				Token::Map { len: Some(2) },
+				Token::Str("secs"),
+				Token::I64(secs),
+				Token::Str("nanos"),
+				Token::I64(nanos),
+				Token::MapEnd,
+			]
+		};
+		let seq = |secs, nanos| [Token::Seq { len: Some(2) }, Token::I64(secs), Token::I64(nanos), Token::SeqEnd];
+		let tokens = [
+			&[Token::Struct { name: "Config", len: 2 }, Token::Str("secs")][..],
+			&map(1, 500_000_000),
+			&[Token::Str("millis")],
+			&seq(0, 1_500_000),
+			&[Token::StructEnd],
+		]
+		.concat();
+
+		assert_de_tokens(&value.readable(), &tokens);
+	}
+
+	#[test]
+	fn default_unit_errors() {
+		assert_de_tokens_error::<Readable<Config>>(
+			&tokens(Token::Str("5min"), Token::U64(0))[..3],
+			"invalid value: string \"5min\", did you mean `5m`?",
+		);
+
+		assert_de_tokens_error::<Readable<Config>>(
+			&tokens(Token::U64(u64::MAX), Token::U64(0))[..3],
+			"invalid value: integer `18446744073709551615`, expected a duration String, an amount of `s` or a `{secs, \
+			 nanos}` pair",
+		);
+	}
+}
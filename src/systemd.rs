@@ -0,0 +1,361 @@
+use std::fmt::{Display, Formatter};
+
+use crate::unit::Unit;
+use crate::{DurationFlex, DurationFlexError, Grammar, NANOS_PER_SEC, SECS_PER_MINUTES};
+
+/// Average month (30.44 days), as defined by systemd.
+const NANOS_PER_MONTH: u64 = 2_629_800 * NANOS_PER_SEC;
+/// Average year (365.25 days), as defined by systemd.
+const NANOS_PER_YEAR: u64 = 31_557_600 * NANOS_PER_SEC;
+
+/// Unit suffixes accepted by systemd, in the order they are matched (so `ms` is tried before `m`).
+const SUFFIXES: [(&str, u64); 32] = [
+	("seconds", NANOS_PER_SEC),
+	("second", NANOS_PER_SEC),
+	("sec", NANOS_PER_SEC),
+	("s", NANOS_PER_SEC),
+	("minutes", SECS_PER_MINUTES as u64 * NANOS_PER_SEC),
+	("minute", SECS_PER_MINUTES as u64 * NANOS_PER_SEC),
+	("min", SECS_PER_MINUTES as u64 * NANOS_PER_SEC),
+	("months", NANOS_PER_MONTH),
+	("month", NANOS_PER_MONTH),
+	("M", NANOS_PER_MONTH),
+	("msec", 1_000_000),
+	("ms", 1_000_000),
+	("m", SECS_PER_MINUTES as u64 * NANOS_PER_SEC),
+	("hours", 3_600 * NANOS_PER_SEC),
+	("hour", 3_600 * NANOS_PER_SEC),
+	("hr", 3_600 * NANOS_PER_SEC),
+	("h", 3_600 * NANOS_PER_SEC),
+	("days", 86_400 * NANOS_PER_SEC),
+	("day", 86_400 * NANOS_PER_SEC),
+	("d", 86_400 * NANOS_PER_SEC),
+	("weeks", 604_800 * NANOS_PER_SEC),
+	("week", 604_800 * NANOS_PER_SEC),
+	("w", 604_800 * NANOS_PER_SEC),
+	("years", NANOS_PER_YEAR),
+	("year", NANOS_PER_YEAR),
+	("y", NANOS_PER_YEAR),
+	("usec", 1_000),
+	("us", 1_000),
+	("μs", 1_000),
+	("µs", 1_000),
+	("nsec", 1),
+	("ns", 1),
+];
+
+/// Units used when formatting, from the largest to the smallest.
+const FORMAT_UNITS: [(&str, u64); 10] = [
+	("y", NANOS_PER_YEAR),
+	("month", NANOS_PER_MONTH),
+	("w", 604_800 * NANOS_PER_SEC),
+	("d", 86_400 * NANOS_PER_SEC),
+	("h", 3_600 * NANOS_PER_SEC),
+	("min", SECS_PER_MINUTES as u64 * NANOS_PER_SEC),
+	("s", NANOS_PER_SEC),
+	("ms", 1_000_000),
+	("us", 1_000),
+	("ns", 1),
+];
+
+/// Formats a [`DurationFlex`] like systemd's `format_timespan`, see [`DurationFlex::systemd`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Systemd {
+	value: DurationFlex,
+	accuracy: Unit,
+}
+
+impl Systemd {
+	pub(crate) fn new(value: DurationFlex) -> Self {
+		Systemd { value, accuracy: Unit::Microsecond }
+	}
+
+	/// Smallest unit to be shown, the remaining is truncated. Defaults to [`Unit::Microsecond`], the precision of
+	/// systemd itself.
+	pub fn accuracy(mut self, value: Unit) -> Self {
+		self.accuracy = value;
+		self
+	}
+}
+
+impl Display for Systemd {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		format(&self.value, self.accuracy.nanos() as u128, f)
+	}
+}
+
+fn is_whitespace(c: char) -> bool {
+	matches!(c, ' ' | '\t' | '\n' | '\r')
+}
+
+/// Parses the systemd.time(7) timespan format, like `2min 30s`, `1h 5min`, `5 hours` or `1y 12month`.
+///
+/// Components are optionally separated by whitespace, can be in any order, repeated (they are summed) and have a
+/// fraction. A number without unit is in seconds. Months are 30.44 days and years are 365.25 days. `infinity` is the
+/// largest duration. Just like systemd, negative durations are not accepted.
+pub(crate) fn parse(input: &str) -> Result<DurationFlex, DurationFlexError> {
+	let garbage = |offset: usize| {
+		DurationFlexError::TrailingGarbage {
+			offset,
+			text: input[offset..].to_string(),
+			suggestion: None,
+			grammar: Grammar::Systemd,
+		}
+	};
+	let skip_whitespace = |offset: usize| input.len() - input[offset..].trim_start_matches(is_whitespace).len();
+
+	let mut offset = skip_whitespace(0);
+	if let Some(rest) = input[offset..].strip_prefix("infinity") {
+		return match rest.trim_start_matches(is_whitespace) {
+			"" => Ok(DurationFlex::MAX),
+			_ => Err(garbage(offset + "infinity".len())),
+		};
+	}
+
+	if offset == input.len() {
+		return Err(DurationFlexError::EmptyInput { grammar: Grammar::Systemd });
+	}
+
+	let mut total = 0i128;
+	while offset < input.len() {
+		let start = offset;
+		let digits_start = if input[offset..].starts_with('+') { offset + 1 } else { offset };
+		let integer_end = digits_start
+			+ input[digits_start..].find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len() - digits_start);
+		let integer = &input[digits_start..integer_end];
+
+		let (fraction, number_end) = match input.as_bytes().get(integer_end) {
+			Some(b'.') if !integer.is_empty() || digits_start == start => {
+				let start = integer_end + 1;
+				let end = start + input[start..].find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len() - start);
+				if start == end {
+					return Err(garbage(integer_end));
+				}
+
+				(&input[start..end], end)
+			},
+			_ if integer.is_empty() => return Err(garbage(start)),
+			_ => ("", integer_end),
+		};
+
+		let unit_start = skip_whitespace(number_end);
+		let (size, unit_end) = match SUFFIXES.iter().find(|(suffix, _)| input[unit_start..].starts_with(suffix)) {
+			Some((suffix, size)) => (*size, unit_start + suffix.len()),
+			None if input[unit_start..].starts_with(char::is_alphabetic) => {
+				let end = unit_start
+					+ input[unit_start..].find(|c: char| !c.is_alphabetic()).unwrap_or(input.len() - unit_start);
+
+				return Err(DurationFlexError::UnknownUnit {
+					offset: unit_start,
+					unit: input[unit_start..end].to_string(),
+					suggestion: None,
+					grammar: Grammar::Systemd,
+				});
+			},
+			None if unit_start == number_end && number_end < input.len() => return Err(garbage(number_end)),
+			None => (NANOS_PER_SEC, number_end),
+		};
+
+		let overflow =
+			|| DurationFlexError::ComponentOverflow { offset: start, text: input[start..unit_end].to_string() };
+		let value = match integer {
+			"" => 0,
+			_ => integer.parse::<u64>().map_err(|_| overflow())?,
+		};
+
+		let mut component = value as i128 * size as i128;
+		let mut scale = size / 10;
+		for digit in fraction.bytes() {
+			if scale == 0 {
+				break;
+			}
+
+			component += (digit - b'0') as i128 * scale as i128;
+			scale /= 10;
+		}
+
+		if DurationFlex::from_total_nanos(component).is_none() {
+			return Err(overflow());
+		}
+
+		total = total.checked_add(component).ok_or(DurationFlexError::OutOfRange)?;
+		offset = skip_whitespace(unit_end);
+	}
+
+	DurationFlex::from_total_nanos(total).ok_or(DurationFlexError::OutOfRange)
+}
+
+/// Formats `value` like systemd's `format_timespan`, e.g. `1h 5min`, `2min 30s` or `1.500000s`. Components smaller
+/// than `accuracy` (in nano-seconds) are truncated. Zero is `0` and the largest duration is `infinity`.
+///
+/// Unlike systemd, negative durations are formatted too, prefixed by `-`.
+fn format(value: &DurationFlex, accuracy: u128, f: &mut Formatter<'_>) -> std::fmt::Result {
+	if *value == DurationFlex::MAX {
+		return f.write_str("infinity");
+	}
+
+	let total = value.total_nanos();
+	if total == 0 {
+		return f.write_str("0");
+	} else if total < 0 {
+		f.write_str("-")?;
+	}
+
+	let mut remaining = total.unsigned_abs();
+	let mut something = false;
+	for (suffix, size) in FORMAT_UNITS {
+		let size = size as u128;
+		if remaining == 0 || (remaining < accuracy && something) {
+			break;
+		} else if remaining < size {
+			continue;
+		}
+
+		let separator = if something { " " } else { "" };
+		let (whole, mut fraction) = (remaining / size, remaining % size);
+		something = true;
+
+		// Below a minute, the remaining is shown as a fraction of the current unit, with as many digits as the accuracy
+		// allows, e.g. `1.500000s`.
+		if remaining < Unit::Minute.nanos() as u128 && fraction > 0 {
+			let mut digits = size.ilog10() as i32;
+			let mut scale = accuracy;
+			while scale > 1 {
+				fraction /= 10;
+				digits -= 1;
+				scale /= 10;
+			}
+
+			if digits > 0 {
+				return write!(f, "{}{}.{:0width$}{}", separator, whole, fraction, suffix, width = digits as usize);
+			}
+		}
+
+		write!(f, "{}{}{}", separator, whole, suffix)?;
+		remaining = fraction;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{flex, DurationFlex, DurationFlexError, Grammar, Unit};
+
+	#[test]
+	fn parse() {
+		assert_eq!(DurationFlex::parse_systemd("2min 30s"), Ok(flex("2m30s")));
+		assert_eq!(DurationFlex::parse_systemd("1h 5min"), Ok(flex("1h5m")));
+		assert_eq!(DurationFlex::parse_systemd("5 hours"), Ok(flex("5h")));
+		assert_eq!(DurationFlex::parse_systemd("100ms"), Ok(flex("100ms")));
+		assert_eq!(DurationFlex::parse_systemd("3 weeks"), Ok(flex("3w")));
+		assert_eq!(DurationFlex::parse_systemd("1y"), Ok(flex("365d6h")));
+		assert_eq!(DurationFlex::parse_systemd("1M"), Ok(flex("30d10h30m")));
+		assert_eq!(DurationFlex::parse_systemd("1y 12month"), Ok(flex("730d12h")));
+
+		// Examples from systemd.time(7).
+		assert_eq!(DurationFlex::parse_systemd("2 h"), Ok(flex("2h")));
+		assert_eq!(DurationFlex::parse_systemd("2hours"), Ok(flex("2h")));
+		assert_eq!(DurationFlex::parse_systemd("48hr"), Ok(flex("2d")));
+		assert_eq!(DurationFlex::parse_systemd("55s500ms"), Ok(flex("55s500ms")));
+		assert_eq!(DurationFlex::parse_systemd("300ms20s 5day"), Ok(flex("5d20s300ms")));
+
+		assert_eq!(DurationFlex::parse_systemd("30"), Ok(flex("30s")));
+		assert_eq!(DurationFlex::parse_systemd(" 1.5h "), Ok(flex("1h30m")));
+		assert_eq!(DurationFlex::parse_systemd(".5s"), Ok(flex("500ms")));
+		assert_eq!(DurationFlex::parse_systemd("12.34s.56"), Ok(flex("12s900ms")));
+		assert_eq!(DurationFlex::parse_systemd("+5s"), Ok(flex("5s")));
+		assert_eq!(DurationFlex::parse_systemd("1usec 2µs 3μs 4nsec"), Ok(flex("6us4ns")));
+		assert_eq!(DurationFlex::parse_systemd("infinity"), Ok(DurationFlex::MAX));
+		assert_eq!(DurationFlex::parse_systemd(" infinity "), Ok(DurationFlex::MAX));
+	}
+
+	#[test]
+	fn parse_errors() {
+		assert_eq!(DurationFlex::parse_systemd(""), Err(DurationFlexError::EmptyInput { grammar: Grammar::Systemd }));
+		assert_eq!(DurationFlex::parse_systemd("  "), Err(DurationFlexError::EmptyInput { grammar: Grammar::Systemd }));
+		assert_eq!(
+			DurationFlex::parse_systemd("-5s"),
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 0,
+				text: "-5s".to_string(),
+				suggestion: None,
+				grammar: Grammar::Systemd
+			})
+		);
+		assert_eq!(
+			DurationFlex::parse_systemd("5 lightyears"),
+			Err(DurationFlexError::UnknownUnit {
+				offset: 2,
+				unit: "lightyears".to_string(),
+				suggestion: None,
+				grammar: Grammar::Systemd
+			})
+		);
+		assert_eq!(
+			DurationFlex::parse_systemd("12.34.56"),
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 5,
+				text: ".56".to_string(),
+				suggestion: None,
+				grammar: Grammar::Systemd
+			})
+		);
+		assert_eq!(
+			DurationFlex::parse_systemd("infinity 1s"),
+			Err(DurationFlexError::TrailingGarbage {
+				offset: 8,
+				text: " 1s".to_string(),
+				suggestion: None,
+				grammar: Grammar::Systemd
+			})
+		);
+
+		assert!(DurationFlex::parse_systemd("5.s").is_err());
+		assert!(DurationFlex::parse_systemd("3. 1").is_err());
+		assert!(DurationFlex::parse_systemd("+.5s").is_err());
+		assert!(DurationFlex::parse_systemd("+ 5s").is_err());
+		assert!(DurationFlex::parse_systemd(".").is_err());
+		assert!(DurationFlex::parse_systemd("99999999999999999999s").is_err());
+		assert!(DurationFlex::parse_systemd("300000000000y").is_err());
+	}
+
+	#[test]
+	fn help() {
+		let help = |input: &str| DurationFlex::parse_systemd(input).unwrap_err().help();
+
+		assert_eq!(
+			help("1 lightyear"),
+			Some("valid units are y, month, w, d, h, min, s, ms, us and ns, or their long names like `minutes`")
+		);
+	}
+
+	#[test]
+	fn format() {
+		assert_eq!(flex("0s").systemd().to_string(), "0");
+		assert_eq!(flex("2m30s").systemd().to_string(), "2min 30s");
+		assert_eq!(flex("1h5m").systemd().to_string(), "1h 5min");
+		assert_eq!(flex("100ms").systemd().to_string(), "100ms");
+		assert_eq!(flex("10d").systemd().to_string(), "1w 3d");
+		assert_eq!(flex("365d6h").systemd().to_string(), "1y");
+		assert_eq!(flex("1s500ms").systemd().to_string(), "1.500000s");
+		assert_eq!(flex("1h1s500ms").systemd().to_string(), "1h 1.500000s");
+		assert_eq!(flex("1ms500us").systemd().to_string(), "1.500ms");
+		assert_eq!(flex("1us500ns").systemd().to_string(), "1us");
+		assert_eq!(flex("-1h5m").systemd().to_string(), "-1h 5min");
+		assert_eq!(DurationFlex::MAX.systemd().to_string(), "infinity");
+
+		assert_eq!(flex("1s500ms").systemd().accuracy(Unit::Millisecond).to_string(), "1.500s");
+		assert_eq!(flex("1s500ms").systemd().accuracy(Unit::Second).to_string(), "1s");
+		assert_eq!(flex("1us500ns").systemd().accuracy(Unit::Nanosecond).to_string(), "1.500us");
+		assert_eq!(flex("1h5m30s").systemd().accuracy(Unit::Minute).to_string(), "1h 5min");
+
+		for value in ["2m30s", "1h5m", "1w3d", "1s500ms", "-1h5m"] {
+			let formatted = flex(value).systemd().to_string();
+			assert_eq!(
+				DurationFlex::parse_systemd(formatted.trim_start_matches('-')).unwrap(),
+				flex(value.trim_start_matches('-'))
+			);
+		}
+	}
+}
@@ -0,0 +1,111 @@
+use crate::{
+	DurationFlex, NANOS_PER_MICRO, NANOS_PER_MILLI, NANOS_PER_SEC, SECS_PER_DAY, SECS_PER_HOUR, SECS_PER_MINUTES,
+	SECS_PER_WEEK,
+};
+
+/// Time units supported by the duration format, from the largest to the smallest.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Unit {
+	/// Weeks (`w`), 7 days.
+	Week,
+	/// Days (`d`), 24 hours.
+	Day,
+	/// Hours (`h`).
+	Hour,
+	/// Minutes (`m`).
+	Minute,
+	/// Seconds (`s`).
+	Second,
+	/// Milli-seconds (`ms`).
+	Millisecond,
+	/// Micro-seconds (`us`).
+	Microsecond,
+	/// Nano-seconds (`ns`).
+	Nanosecond,
+}
+
+impl Unit {
+	/// All units, in the order they must appear in a duration string.
+	pub(crate) const ALL: [Unit; 8] = [
+		Unit::Week,
+		Unit::Day,
+		Unit::Hour,
+		Unit::Minute,
+		Unit::Second,
+		Unit::Millisecond,
+		Unit::Microsecond,
+		Unit::Nanosecond,
+	];
+
+	/// Canonical symbol, as used by [`std::fmt::Display`].
+	pub fn symbol(self) -> &'static str {
+		match self {
+			Unit::Week => "w",
+			Unit::Day => "d",
+			Unit::Hour => "h",
+			Unit::Minute => "m",
+			Unit::Second => "s",
+			Unit::Millisecond => "ms",
+			Unit::Microsecond => "us",
+			Unit::Nanosecond => "ns",
+		}
+	}
+
+	/// Parses a unit symbol. Besides the canonical ones, accepts `µs` (micro sign) and `μs` (greek mu).
+	pub(crate) fn from_symbol(symbol: &str) -> Option<Unit> {
+		match symbol {
+			"w" => Some(Unit::Week),
+			"d" => Some(Unit::Day),
+			"h" => Some(Unit::Hour),
+			"m" => Some(Unit::Minute),
+			"s" => Some(Unit::Second),
+			"ms" => Some(Unit::Millisecond),
+			"us" | "µs" | "μs" => Some(Unit::Microsecond),
+			"ns" => Some(Unit::Nanosecond),
+			_ => None,
+		}
+	}
+
+	/// Parses a long unit name or abbreviation, like `hours`, `hr` or `min`, in lower case.
+	pub(crate) fn from_name(name: &str) -> Option<Unit> {
+		match name {
+			"week" | "weeks" | "wk" | "wks" => Some(Unit::Week),
+			"day" | "days" => Some(Unit::Day),
+			"hour" | "hours" | "hr" | "hrs" => Some(Unit::Hour),
+			"minute" | "minutes" | "min" | "mins" => Some(Unit::Minute),
+			"second" | "seconds" | "sec" | "secs" => Some(Unit::Second),
+			"millisecond" | "milliseconds" | "milli" | "millis" | "msec" | "msecs" => Some(Unit::Millisecond),
+			"microsecond" | "microseconds" | "micro" | "micros" | "usec" | "usecs" => Some(Unit::Microsecond),
+			"nanosecond" | "nanoseconds" | "nano" | "nanos" | "nsec" | "nsecs" => Some(Unit::Nanosecond),
+			_ => None,
+		}
+	}
+
+	/// Amount of nano-seconds in one of this unit.
+	pub(crate) fn nanos(self) -> u64 {
+		match self {
+			Unit::Week => SECS_PER_WEEK as u64 * NANOS_PER_SEC,
+			Unit::Day => SECS_PER_DAY as u64 * NANOS_PER_SEC,
+			Unit::Hour => SECS_PER_HOUR as u64 * NANOS_PER_SEC,
+			Unit::Minute => SECS_PER_MINUTES as u64 * NANOS_PER_SEC,
+			Unit::Second => NANOS_PER_SEC,
+			Unit::Millisecond => NANOS_PER_MILLI,
+			Unit::Microsecond => NANOS_PER_MICRO,
+			Unit::Nanosecond => 1,
+		}
+	}
+
+	/// Length of one of this unit.
+	pub(crate) fn duration(self) -> DurationFlex {
+		match self {
+			Unit::Week => DurationFlex::WEEK,
+			Unit::Day => DurationFlex::DAY,
+			Unit::Hour => DurationFlex::HOUR,
+			Unit::Minute => DurationFlex::MINUTE,
+			Unit::Second => DurationFlex::SECOND,
+			Unit::Millisecond => DurationFlex::MILLISECOND,
+			Unit::Microsecond => DurationFlex::MICROSECOND,
+			Unit::Nanosecond => DurationFlex::NANOSECOND,
+		}
+	}
+}